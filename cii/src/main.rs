@@ -1,24 +1,29 @@
 use std::{
     env,
     process::exit,
-    fs,
-    io::{self, BufRead, Write}
+    io::{self, BufRead, Write},
+    path::Path
 };
 
 use platinum_core::lexer::lexer::*;
 use platinum_core::parser::parser::Parser;
+use platinum_core::driver::session::Driver;
 // use interpriter::interpreter::Interpreter;
 
 /// Runs file
 /// Use `metal run <filename>` to run
 fn run_file(path: &str) -> Result<(), String> {
     // let mut interpreter = Interpreter::new();
-    
-    // Reads file first
-    match fs::read_to_string(path) {
-        Err(msg) => return Err(msg.to_string()),
-        Ok(src) => run(path, &src),
+
+    // Imported modules are searched next to the file
+    let root = Path::new(path).parent().map(|parent| parent.display().to_string()).unwrap_or_default();
+    let mut driver = Driver::new(&root);
+    for file in driver.parse_program(path)? {
+        for statement in file.statements.iter() {
+            println!("{}", statement.to_string());
+        }
     }
+    Ok(())
 }
 
 /// Runs script from console
@@ -78,7 +83,7 @@ fn main() {
 
     let args: Vec<String> = env::args().collect();
 
-    if args.len() > 1 {
+    if args.len() > 2 {
         println!("Usage: metal [script]");
         exit(64);
     } else if args.len() == 2 {
@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+pub const EXTENSION: &str = "ppl";
+
+/// Maps module paths to files, `math.vector` is `<root>/math/vector.ppl`
+pub struct Resolver {
+    root: PathBuf,
+}
+
+impl Resolver {
+    pub fn new(root: &str) -> Self {
+        Self { root: PathBuf::from(root) }
+    }
+
+    /// Returns file of module, it may not exist
+    pub fn file_of(&self, module: &str) -> PathBuf {
+        let mut file = self.root.clone();
+        for part in module.split('.') {
+            file.push(part);
+        }
+        file.set_extension(EXTENSION);
+        file
+    }
+
+    /// Returns file of module, error if there is no such file
+    pub fn resolve(&self, module: &str) -> Result<PathBuf, String> {
+        let file = self.file_of(module);
+        if !file.is_file() {
+            return Err(format!("Module `{}` not found, expected file `{}`", module, file.display()));
+        }
+        Ok(file)
+    }
+
+    /// Returns name of module in `file`, files outside of root are named by their stem
+    pub fn module_of(&self, file: &Path) -> String {
+        let relative = file.strip_prefix(&self.root).unwrap_or(file).with_extension("");
+        if file.strip_prefix(&self.root).is_err() {
+            return relative.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        }
+        let parts: Vec<String> = relative.components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect();
+        parts.join(".")
+    }
+}
@@ -0,0 +1,144 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{check_program, lexer::lexer::Scanner, parser::{parser::Parser, stmt::{module_name, Statement, Visibility}}};
+
+use super::resolver::Resolver;
+
+#[derive(Debug)]
+pub struct SourceFile {
+    pub module: String,
+    pub file_path: String,
+    pub statements: Vec<Statement>,
+}
+
+/// Parses and checks programs made of many files
+pub struct Driver {
+    resolver: Resolver,
+    /// Parsers of loaded modules, they keep declarations which can be imported
+    parsers: HashMap<String, Parser>,
+    files: Vec<SourceFile>,
+    /// Modules which are being loaded, used to find import cycles
+    loading: Vec<String>,
+}
+
+impl Driver {
+    /// Modules are searched relative to `root` directory
+    pub fn new(root: &str) -> Self {
+        Self {
+            resolver: Resolver::new(root),
+            parsers: HashMap::new(),
+            files: vec![],
+            loading: vec![],
+        }
+    }
+
+    /// Parses `entry` file and all modules imported from it,
+    /// imported modules go before files which import them
+    pub fn parse_program(&mut self, entry: &str) -> Result<Vec<SourceFile>, String> {
+        self.parsers.clear();
+        self.files.clear();
+        self.loading.clear();
+        let entry = Path::new(entry);
+        let module = self.resolver.module_of(entry);
+        self.load(&module, entry)?;
+        Ok(std::mem::take(&mut self.files))
+    }
+
+    fn load(&mut self, module: &str, file: &Path) -> Result<(), String> {
+        let file_path = file.display().to_string();
+        let src = fs::read_to_string(file).map_err(|msg| format!("{}: {}", file_path, msg))?;
+        let mut scanner = Scanner::new(&file_path, &src);
+        scanner.scan_tokens()?;
+        let mut parser = Parser::new(&file_path, scanner.tokens);
+
+        let mut statements = parser.parse_header()?;
+        let mut imported_declarations = vec![];
+        self.loading.push(module.to_string());
+        for statement in &statements {
+            match statement {
+                Statement::Module { path } if module_name(path) != module => {
+                    return Err(format!("{}: File declares module `{}`, expected `{}`", file_path, module_name(path), module));
+                }
+                Statement::Import { path, names } => {
+                    let imported = module_name(path);
+                    self.import(&imported, &file_path)?;
+                    let declared = declarations(&self.file(&imported).statements);
+                    let mut imported_names = vec![];
+                    for name in names {
+                        match declared.iter().find(|(declared, _)| declared == &name.lexeme) {
+                            Some((_, Visibility::Public)) => imported_names.push(name.lexeme.clone()),
+                            Some(_) => {
+                                return Err(format!("{}: `{}` isn't `pub` in module `{}`", file_path, name.lexeme, imported));
+                            }
+                            None => {
+                                return Err(format!("{}: Module `{}` has no declaration `{}`", file_path, imported, name.lexeme));
+                            }
+                        }
+                    }
+                    if names.is_empty() {
+                        imported_names = declared.into_iter()
+                            .filter(|(_, visibility)| *visibility == Visibility::Public)
+                            .map(|(name, _)| name)
+                            .collect();
+                    }
+                    parser.import_declarations(&self.parsers[&imported], &imported_names);
+                    imported_declarations.extend(declarations_of(&self.file(&imported).statements, &imported_names));
+                }
+                _ => {}
+            }
+        }
+        self.loading.pop();
+
+        let mut program = parser.parse_program()?;
+        check_program(&file_path, &mut program, &imported_declarations)?;
+        statements.extend(program.into_statements());
+        self.parsers.insert(module.to_string(), parser);
+        self.files.push(SourceFile { module: module.to_string(), file_path, statements });
+        Ok(())
+    }
+
+    /// Loads module imported in `file_path` if it isn't loaded yet
+    fn import(&mut self, module: &str, file_path: &str) -> Result<(), String> {
+        if let Some(start) = self.loading.iter().position(|loading| loading == module) {
+            let mut cycle = self.loading[start..].to_vec();
+            cycle.push(module.to_string());
+            return Err(format!("{}: Import cycle `{}`", file_path, cycle.join(" -> ")));
+        }
+        if self.parsers.contains_key(module) {
+            return Ok(());
+        }
+        let file = self.resolver.resolve(module).map_err(|msg| format!("{}: {}", file_path, msg))?;
+        self.load(module, &file)
+    }
+
+    fn file(&self, module: &str) -> &SourceFile {
+        self.files.iter().find(|file| file.module == module).expect("imported module is loaded")
+    }
+}
+
+fn declarations(statements: &[Statement]) -> Vec<(String, Visibility)> {
+    let mut declarations = vec![];
+    for statement in statements {
+        match statement {
+            Statement::Const { name, visibility, .. } |
+            Statement::Static { name, visibility, .. } |
+            Statement::Function { name, visibility, .. } |
+            Statement::Enum { name, visibility, .. } |
+            Statement::Interface { name, visibility, .. } |
+            Statement::Class { name, visibility, .. } => declarations.push((name.lexeme.clone(), *visibility)),
+            Statement::Let { pattern, visibility, .. } => {
+                declarations.extend(pattern.bindings().iter().map(|name| (name.lexeme.clone(), *visibility)));
+            }
+            _ => {}
+        }
+    }
+    declarations
+}
+
+/// Returns statements which declare some of `names`
+fn declarations_of(statements: &[Statement], names: &[String]) -> Vec<Statement> {
+    statements.iter()
+        .filter(|statement| declarations(std::slice::from_ref(*statement)).iter().any(|(name, _)| names.contains(name)))
+        .cloned()
+        .collect()
+}
@@ -35,9 +35,13 @@ fn str_to_keyword(string: &str) -> Option<TokenType> {
     match string {
         "and" => Some(And),
         "or" => Some(Or),
+        "not" => Some(Bang),
         "if" => Some(If),
         "else" => Some(Else),
+        "match" => Some(Match),
         "class" => Some(Class),
+        "enum" => Some(Enum),
+        "interface" => Some(Interface),
         "super" => Some(Super),
         "this" => Some(This),
         "fun" => Some(Fun),
@@ -52,6 +56,13 @@ fn str_to_keyword(string: &str) -> Option<TokenType> {
         "continue" => Some(Continue),
         "let" => Some(Let),
         "mut" => Some(Mut),
+        "const" => Some(Const),
+        "static" => Some(Static),
+        "module" => Some(Module),
+        "import" => Some(Import),
+        "from" => Some(From),
+        "pub" => Some(Pub),
+        "private" => Some(Private),
         "true" => Some(BoolT { value: true }),
         "false" => Some(BoolT { value:false }),
 
@@ -143,7 +154,7 @@ impl<'s> Scanner<'s> {
             '}' => RightCurBrace,
             ',' => Comma,
             ';' => Semicolon,
-            ':' => Colon,
+            '#' => Hash,
             '~' => Tilde
         );
 
@@ -164,7 +175,11 @@ impl<'s> Scanner<'s> {
             }
             '^' => {
                 let token = {
-                    Caret
+                    if self.char_match('=') {
+                        CaretEqual
+                    } else {
+                        Caret
+                    }
                 };
                 self.add_token(token, (self.line, pos_start, self.get_pos()));
             }
@@ -193,7 +208,11 @@ impl<'s> Scanner<'s> {
             '.' => {
                 let token = {
                     if self.char_match('.') {
-                        DotDot
+                        if self.char_match('=') {
+                            DotDotEqual
+                        } else {
+                            DotDot
+                        }
                     } else {
                         Dot
                     }
@@ -289,6 +308,8 @@ impl<'s> Scanner<'s> {
                 let token = {
                     if self.char_match('&') {
                         And
+                    } else if self.char_match('=') {
+                        AmpersantEqual
                     } else {
                         Ampersant
                     }
@@ -299,6 +320,8 @@ impl<'s> Scanner<'s> {
                 let token = {
                     if self.char_match('|') {
                         Or
+                    } else if self.char_match('=') {
+                        BarEqual
                     } else {
                         Bar
                     }
@@ -307,7 +330,27 @@ impl<'s> Scanner<'s> {
             }
             '?' => {
                 let token = {
-                    Question
+                    if self.char_match('?') {
+                        if self.char_match('=') {
+                            QuestionQuestionEqual
+                        } else {
+                            QuestionQuestion
+                        }
+                    } else if self.char_match('.') {
+                        QuestionDot
+                    } else {
+                        Question
+                    }
+                };
+                self.add_token(token, (self.line, pos_start, self.get_pos()));
+            }
+            ':' => {
+                let token = {
+                    if self.char_match(':') {
+                        ColonColon
+                    } else {
+                        Colon
+                    }
                 };
                 self.add_token(token, (self.line, pos_start, self.get_pos()));
             }
@@ -315,6 +358,8 @@ impl<'s> Scanner<'s> {
                 let token = {
                     if self.char_match('=') {
                         EqualEqual
+                    } else if self.char_match('>') {
+                        EqualGreater
                     } else {
                         Equal
                     }
@@ -333,9 +378,14 @@ impl<'s> Scanner<'s> {
                 }
             }
             '\'' => {
-                match self.char() {
-                    Ok(_) => (),
-                    Err(msg) => return Err(msg)
+                // `'outer` is a label, `'a'` is a char
+                if is_idetifier_char_start(self.next) && self.peek_next() != '\'' {
+                    self.label();
+                } else {
+                    match self.char() {
+                        Ok(_) => (),
+                        Err(msg) => return Err(msg)
+                    }
                 }
             }
             c => {
@@ -365,6 +415,18 @@ impl<'s> Scanner<'s> {
         Ok(())
     }
 
+    fn label(&mut self) {
+        let pos_start = self.get_pos() - 1;
+
+        let mut buffer = String::new();
+        while is_idetifier_char(self.next) && !self.is_at_end() {
+            self.advance();
+            buffer.push(self.current);
+        }
+
+        self.add_token(Label { value: buffer }, (self.line, pos_start, self.get_pos()));
+    }
+
     fn identifier(&mut self) -> Result<(), String> {
         let pos_start = self.get_pos() - 1;
 
@@ -546,7 +608,8 @@ impl<'s> Scanner<'s> {
             if self.is_at_end() {
                 break;
             }
-            if self.next.is_digit(10) || self.next == '.' || self.next == '_' {
+            // `1..2` is a range, not a float with two dots
+            if self.next.is_ascii_digit() || (self.next == '.' && self.peek_next() != '.') || self.next == '_' {
                 self.advance();
             } else {
                 break;
@@ -753,6 +816,11 @@ impl<'s> Scanner<'s> {
         self.next = next_char;
     }
 
+    /// Returns char after `next` without advancing
+    fn peek_next(&self) -> char {
+        self.chars.clone().next().unwrap_or('\0')
+    }
+
     fn char_match(&mut self, expected: char) -> bool {
         if self.next != expected {
             return false;
@@ -34,8 +34,11 @@ pub enum TokenType {
     Comma,          // ,
     Dot,            // .
     DotDot,         // ..
+    DotDotEqual,    // ..=
     Semicolon,      // ;
     Colon,          // :
+    ColonColon,     // ::
+    Hash,           // #
     
     Minus,          // -
     Plus,           // +
@@ -67,14 +70,21 @@ pub enum TokenType {
     LessLessEqual,          // <<=
     Tilde,                  // ~
 
+    AmpersantEqual, // &=
+    BarEqual,       // |=
+    CaretEqual,     // ^=
+
     Question,           // ?
     QuestionQuestion,   // ??
+    QuestionQuestionEqual, // ??=
+    QuestionDot,        // ?.
     Ampersant,      // &
     Bar,            // |
     Caret,          // ^
 
     
     MinusGreater,   // ->
+    EqualGreater,   // =>
     
 
     // Literals.
@@ -84,6 +94,8 @@ pub enum TokenType {
     Int { value: String, num_type: NumberType },
     Float { value: String, num_type: NumberType },
     BoolT { value: bool },
+    /// `'outer` names a loop
+    Label { value: String },
 
     DocComent,
     Coment,
@@ -94,8 +106,11 @@ pub enum TokenType {
 
     If,
     Else,
+    Match,
 
     Class,
+    Enum,
+    Interface,
     Super,
     This,
 
@@ -114,6 +129,14 @@ pub enum TokenType {
     Null,
     Let,
     Mut,
+    Const,
+    Static,
+
+    Module,
+    Import,
+    From,
+    Pub,
+    Private,
     
     DBG, // Turn of on release
     
@@ -129,7 +152,8 @@ impl TokenType {
             (TokenType::Char {..}, TokenType::Char {..}) |
             (TokenType::Int {..}, TokenType::Int {..}) |
             (TokenType::Float {..}, TokenType::Float {..}) |
-            (TokenType::BoolT {..}, TokenType::BoolT {..}) => return true,
+            (TokenType::BoolT {..}, TokenType::BoolT {..}) |
+            (TokenType::Label {..}, TokenType::Label {..}) => true,
             _ => {
                 return self.eq(&token);
             }
@@ -1,3 +1,32 @@
-
 pub mod lexer;
 pub mod parser;
+pub mod driver;
+pub mod passes;
+
+use lexer::lexer::Scanner;
+use parser::{parser::Parser, stmt::{Program, Statement}};
+use passes::manager::PassManager;
+
+/// Scans and parses source of file at `path`
+pub fn parse_str(path: &str, src: &str) -> Result<Program, String> {
+    let mut scanner = Scanner::new(path, src);
+    scanner.scan_tokens()?;
+    Parser::new(path, scanner.tokens).parse_program()
+}
+
+/// Runs standard passes over `program` parsed from file at `path`,
+/// `imported` are declarations which the file imports from other modules
+pub fn check_program(path: &str, program: &mut Program, imported: &[Statement]) -> Result<(), String> {
+    PassManager::standard_with(imported).run(program).map_err(|errors| {
+        errors.lines().map(|msg| format!("{}: {}\n", path, msg)).collect()
+    })
+}
+
+impl Parser {
+    /// Parses whole file and runs standard passes over it, returns statements without splitting them into items
+    pub fn parse(&mut self) -> Result<Vec<Statement>, String> {
+        let mut program = self.parse_program()?;
+        check_program(self.file_path(), &mut program, &[])?;
+        Ok(program.into_statements())
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+
+use crate::lexer::token::{Token, TokenType};
+
+use super::{expr::Expression, stmt::{Argument, Statement, UseArgument}};
+
+/// Outer variable used inside of lambda
+#[derive(Debug, PartialEq, Clone)]
+pub struct Capture {
+    pub name: Token,
+    /// Lambda assigns to variable (`a = 1`, `a += 1`, `a++`, `a[0] = 1`)
+    pub mutated: bool,
+}
+
+impl fmt::Display for Capture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", if self.mutated { "mut " } else { "" }, self.name.lexeme)
+    }
+}
+
+/// Returns variables which are used in `body` but are declared outside of lambda
+pub fn find_captures(arguments: &Vec<Argument>, body: &Statement) -> Vec<Capture> {
+    let mut finder = CaptureFinder { scopes: vec![vec![]], captures: vec![] };
+    finder.arguments(arguments);
+    finder.statement(body);
+    finder.captures
+}
+
+struct CaptureFinder {
+    scopes: Vec<Vec<String>>,
+    captures: Vec<Capture>,
+}
+
+impl CaptureFinder {
+    fn declare(&mut self, name: &Token) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(name.lexeme.clone());
+        }
+    }
+
+    fn reference(&mut self, name: &Token, mutated: bool) {
+        if name.token_type == TokenType::This || self.scopes.iter().any(|scope| scope.contains(&name.lexeme)) {
+            return;
+        }
+        match self.captures.iter_mut().find(|capture| capture.name.lexeme == name.lexeme) {
+            Some(capture) => capture.mutated |= mutated,
+            None => self.captures.push(Capture { name: name.clone(), mutated }),
+        }
+    }
+
+    fn arguments(&mut self, arguments: &Vec<Argument>) {
+        for argument in arguments {
+            match argument {
+                Argument::NotOptional { name, .. } => self.declare(name),
+                Argument::Optional { name, value, .. } => {
+                    self.expression(value);
+                    self.declare(name);
+                }
+            }
+        }
+    }
+
+    fn use_arguments(&mut self, arguments: &Vec<UseArgument>) {
+        for argument in arguments {
+            match argument {
+                UseArgument::Expr { value } |
+                UseArgument::Optional { value, .. } => self.expression(value),
+            }
+        }
+    }
+
+    fn scoped(&mut self, statement: &Statement) {
+        self.scopes.push(vec![]);
+        self.statement(statement);
+        self.scopes.pop();
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block { statements } => {
+                self.scopes.push(vec![]);
+                for statement in statements {
+                    self.statement(statement);
+                }
+                self.scopes.pop();
+            }
+            Statement::Assigment { expression } => self.expression(expression),
+            Statement::Let { pattern, value, .. } => {
+                if let Some(value) = value {
+                    self.expression(value);
+                }
+                for name in pattern.bindings() {
+                    self.declare(&name);
+                }
+            }
+            Statement::Const { name, value, .. } |
+            Statement::Static { name, value, .. } => {
+                self.expression(value);
+                self.declare(name);
+            }
+            Statement::Function { name, arguments, block, .. } => {
+                self.declare(name);
+                self.scopes.push(vec![]);
+                self.arguments(arguments);
+                self.statement(block);
+                self.scopes.pop();
+            }
+            Statement::FunctionUse { name, arguments } => {
+                self.reference(name, false);
+                self.use_arguments(arguments);
+            }
+            Statement::IfElse { condition, if_block, else_block } => {
+                self.expression(condition);
+                self.scoped(if_block);
+                if let Some(else_block) = else_block {
+                    self.scoped(else_block);
+                }
+            }
+            Statement::Loop { block, .. } => self.scoped(block),
+            Statement::For { pattern, container, block, .. } => {
+                self.statement(container);
+                self.scopes.push(pattern.bindings().iter().map(|name| name.lexeme.clone()).collect());
+                self.statement(block);
+                self.scopes.pop();
+            }
+            Statement::While { condition, block, .. } |
+            Statement::DoWhile { block, condition, .. } => {
+                self.expression(condition);
+                self.scoped(block);
+            }
+            Statement::Return { returned } => {
+                if let Some(returned) = returned {
+                    self.expression(returned);
+                }
+            }
+            Statement::Enum { name, .. } |
+            Statement::Interface { name, .. } |
+            Statement::Class { name, .. } => self.declare(name),
+            Statement::Import { names, .. } => {
+                for name in names {
+                    self.declare(name);
+                }
+            }
+            Statement::Break { value, .. } => {
+                if let Some(value) = value {
+                    self.expression(value);
+                }
+            }
+            Statement::Module { .. } |
+            Statement::Continue { .. } => {}
+        }
+    }
+
+    /// Marks variable at the root of `a`, `a.b` or `a[0]` as mutated
+    fn place(&mut self, target: &Expression) {
+        match target {
+            Expression::Variable { name } => self.reference(name, true),
+            Expression::Get { object, .. } |
+            Expression::TupleIndex { object, .. } => self.place(object),
+            Expression::Index { object, index } => {
+                self.place(object);
+                self.expression(index);
+            }
+            _ => self.expression(target),
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Binary { left, right, .. } |
+            Expression::EqualtyComparison { left, right, .. } |
+            Expression::Logical { left, right, .. } |
+            Expression::NullCoalescing { left, right, .. } => {
+                self.expression(left);
+                self.expression(right);
+            }
+            Expression::Ternary { result, true_expression, false_expression } => {
+                self.expression(result);
+                self.expression(true_expression);
+                self.expression(false_expression);
+            }
+            Expression::Unary { right, .. } => self.expression(right),
+            Expression::Grouping { expression } => self.expression(expression),
+            Expression::Assign { target, value, .. } => {
+                self.place(target);
+                self.expression(value);
+            }
+            Expression::Increment { target, .. } => self.place(target),
+            Expression::Get { object, .. } |
+            Expression::TupleIndex { object, .. } => self.expression(object),
+            Expression::Call { callee, arguments, .. } => {
+                self.expression(callee);
+                self.use_arguments(arguments);
+            }
+            Expression::Array { elements } |
+            Expression::Tuple { elements } => {
+                for element in elements {
+                    self.expression(element);
+                }
+            }
+            Expression::ArrayRepeat { value, count } => {
+                self.expression(value);
+                self.expression(count);
+            }
+            Expression::Index { object, index } => {
+                self.expression(object);
+                self.expression(index);
+            }
+            Expression::Map { entries } => {
+                for (key, value) in entries {
+                    self.expression(key);
+                    self.expression(value);
+                }
+            }
+            Expression::Range { start, end, .. } => {
+                for bound in [start, end].into_iter().flatten() {
+                    self.expression(bound);
+                }
+            }
+            Expression::Lambda { captures, .. } => {
+                // Nested lambda already knows what it takes from outside
+                for capture in captures {
+                    self.reference(&capture.name, capture.mutated);
+                }
+            }
+            Expression::Match { value, arms } => {
+                self.expression(value);
+                for arm in arms {
+                    self.scopes.push(arm.pattern.bindings().iter().map(|name| name.lexeme.clone()).collect());
+                    if let Some(guard) = &arm.guard {
+                        self.expression(guard);
+                    }
+                    self.statement(&arm.body);
+                    self.scopes.pop();
+                }
+            }
+            Expression::EnumVariant { fields, .. } => {
+                for value in fields.values() {
+                    self.expression(value);
+                }
+            }
+            Expression::Block { statements, value } => {
+                self.scopes.push(vec![]);
+                for statement in statements {
+                    self.statement(statement);
+                }
+                if let Some(value) = value {
+                    self.expression(value);
+                }
+                self.scopes.pop();
+            }
+            Expression::If { condition, then_branch, else_branch } => {
+                self.expression(condition);
+                self.expression(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.expression(else_branch);
+                }
+            }
+            Expression::Loop { body, .. } => self.expression(body),
+            Expression::Variable { name } => self.reference(name, false),
+            Expression::Literal { .. } => {}
+        }
+    }
+}
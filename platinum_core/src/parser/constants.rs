@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use super::expr::Expression;
+
+/// Replaces names of constants in `expression` with their values
+pub fn substitute(expression: &Expression, constants: &HashMap<String, Expression>) -> Expression {
+    let boxed = |expression: &Expression| Box::from(substitute(expression, constants));
+    let all = |expressions: &Vec<Expression>| expressions.iter().map(|expression| substitute(expression, constants)).collect();
+    match expression {
+        Expression::Variable { name } => constants.get(&name.lexeme).cloned().unwrap_or(expression.clone()),
+        Expression::Binary { left, operator, right } => {
+            Expression::Binary { left: boxed(left), operator: operator.clone(), right: boxed(right) }
+        }
+        Expression::EqualtyComparison { left, operator, right } => {
+            Expression::EqualtyComparison { left: boxed(left), operator: operator.clone(), right: boxed(right) }
+        }
+        Expression::Logical { left, operator, right } => {
+            Expression::Logical { left: boxed(left), operator: operator.clone(), right: boxed(right) }
+        }
+        Expression::NullCoalescing { left, operator, right } => {
+            Expression::NullCoalescing { left: boxed(left), operator: operator.clone(), right: boxed(right) }
+        }
+        Expression::Ternary { result, true_expression, false_expression } => {
+            Expression::Ternary {
+                result: boxed(result),
+                true_expression: boxed(true_expression),
+                false_expression: boxed(false_expression)
+            }
+        }
+        Expression::Unary { operator, right } => Expression::Unary { operator: operator.clone(), right: boxed(right) },
+        Expression::Grouping { expression } => Expression::Grouping { expression: boxed(expression) },
+        Expression::Array { elements } => Expression::Array { elements: all(elements) },
+        Expression::Tuple { elements } => Expression::Tuple { elements: all(elements) },
+        Expression::ArrayRepeat { value, count } => Expression::ArrayRepeat { value: boxed(value), count: boxed(count) },
+        Expression::Index { object, index } => Expression::Index { object: boxed(object), index: boxed(index) },
+        Expression::TupleIndex { object, index } => Expression::TupleIndex { object: boxed(object), index: index.clone() },
+        Expression::Range { start, end, inclusive } => {
+            Expression::Range {
+                start: start.as_ref().map(|start| boxed(start)),
+                end: end.as_ref().map(|end| boxed(end)),
+                inclusive: *inclusive
+            }
+        }
+        // Calls, lambdas and assignments are never constant
+        _ => expression.clone(),
+    }
+}
+
+/// Returns true if value of `expression` with substituted constants is known without running program
+pub fn is_constant(expression: &Expression) -> bool {
+    match expression {
+        Expression::Literal { .. } => true,
+        Expression::Grouping { expression } |
+        Expression::Unary { right: expression, .. } |
+        Expression::TupleIndex { object: expression, .. } => is_constant(expression),
+        Expression::Binary { left, right, .. } |
+        Expression::EqualtyComparison { left, right, .. } |
+        Expression::Logical { left, right, .. } |
+        Expression::NullCoalescing { left, right, .. } |
+        Expression::Index { object: left, index: right } => is_constant(left) && is_constant(right),
+        Expression::Ternary { result, true_expression, false_expression } => {
+            is_constant(result) && is_constant(true_expression) && is_constant(false_expression)
+        }
+        Expression::Array { elements } |
+        Expression::Tuple { elements } => elements.iter().all(is_constant),
+        Expression::ArrayRepeat { value, count } => is_constant(value) && is_constant(count),
+        Expression::Range { start, end, .. } => [start, end].into_iter().flatten().all(|bound| is_constant(bound)),
+        Expression::EnumVariant { fields, .. } => fields.values().into_iter().all(is_constant),
+        _ => false,
+    }
+}
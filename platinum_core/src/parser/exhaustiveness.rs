@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use crate::lexer::token::{NumberType, Token, TokenType};
+
+use super::{expr::MatchArm, pattern::Pattern, stmt::{Variant, VariantFields}};
+
+/// Values matched by pattern
+#[derive(Debug, PartialEq, Clone)]
+enum Space {
+    Any,
+    Bool(bool),
+    /// Inclusive range of integers or char codes, `bounds` are min and max values of the type
+    Int { start: i128, end: i128, bounds: Option<(i128, i128)> },
+    /// Strings, floats and `null`, they can't be covered without `_`
+    Other(String),
+    Tuple(Vec<Space>),
+    /// Variant with `index` of enum, `arities` are numbers of fields of all variants
+    Variant { index: usize, arities: Vec<usize>, fields: Vec<Space> },
+}
+
+/// Checks that `match` arms cover all values and that every arm can be reached
+pub fn check_arms(arms: &Vec<MatchArm>, enums: &HashMap<String, Vec<Variant>>) -> Result<(), String> {
+    let mut rows: Vec<Vec<Space>> = vec![];
+    for arm in arms {
+        let space = lower(&arm.pattern, enums)?;
+        if !useful(&rows, std::slice::from_ref(&space)) {
+            return Err(format!("Unreachable match arm `{}`", arm.pattern));
+        }
+        // Arm with guard can fail, so it doesn't cover its pattern
+        if arm.guard.is_none() {
+            rows.push(vec![space]);
+        }
+    }
+    if useful(&rows, &[Space::Any]) {
+        return Err("Non-exhaustive match, add `_` arm to cover all other values".to_string());
+    }
+    Ok(())
+}
+
+/// Returns min and max values of integer type, `None` if type isn't known
+fn int_bounds(num_type: NumberType) -> Option<(i128, i128)> {
+    match num_type {
+        NumberType::I8 => Some((i8::MIN as i128, i8::MAX as i128)),
+        NumberType::I16 => Some((i16::MIN as i128, i16::MAX as i128)),
+        NumberType::I32 => Some((i32::MIN as i128, i32::MAX as i128)),
+        NumberType::I64 | NumberType::ISize => Some((i64::MIN as i128, i64::MAX as i128)),
+        NumberType::U8 => Some((0, u8::MAX as i128)),
+        NumberType::U16 => Some((0, u16::MAX as i128)),
+        NumberType::U32 => Some((0, u32::MAX as i128)),
+        NumberType::U64 | NumberType::USize => Some((0, u64::MAX as i128)),
+        _ => None,
+    }
+}
+
+/// Returns value of range bound and bounds of its type
+fn bound_value(token: &Token) -> Result<(i128, Option<(i128, i128)>), String> {
+    match &token.token_type {
+        TokenType::Int { value, num_type } => {
+            let number = value.parse::<i128>().map_err(|_| format!("Integer `{}` is too large for pattern", token.lexeme))?;
+            Ok((number, int_bounds(*num_type)))
+        }
+        TokenType::Char { value } => Ok((*value as i128, Some((0, char::MAX as i128)))),
+        _ => Err(format!("Range pattern bounds should be integers or chars, found `{}`", token.lexeme)),
+    }
+}
+
+fn lower(pattern: &Pattern, enums: &HashMap<String, Vec<Variant>>) -> Result<Space, String> {
+    match pattern {
+        Pattern::Binding { .. } | Pattern::Wildcard => Ok(Space::Any),
+        Pattern::Literal { value } => {
+            match &value.token_type {
+                TokenType::BoolT { value } => Ok(Space::Bool(*value)),
+                TokenType::Int { .. } | TokenType::Char { .. } => {
+                    let (number, bounds) = bound_value(value)?;
+                    Ok(Space::Int { start: number, end: number, bounds })
+                }
+                _ => Ok(Space::Other(value.lexeme.clone())),
+            }
+        }
+        Pattern::Range { start, end, inclusive } => {
+            let mut bounds = None;
+            let mut start_value = None;
+            let mut end_value = None;
+            if let Some(start) = start {
+                let (number, start_bounds) = bound_value(start)?;
+                start_value = Some(number);
+                bounds = bounds.or(start_bounds);
+            }
+            if let Some(end) = end {
+                let (number, end_bounds) = bound_value(end)?;
+                end_value = Some(if *inclusive { number } else { number.saturating_sub(1) });
+                bounds = bounds.or(end_bounds);
+            }
+            let (min, max) = bounds.unwrap_or((i128::MIN, i128::MAX));
+            let start_value = start_value.unwrap_or(min);
+            let end_value = end_value.unwrap_or(max);
+            if start_value > end_value {
+                return Err(format!("Range pattern `{}` is empty", pattern));
+            }
+            Ok(Space::Int { start: start_value, end: end_value, bounds })
+        }
+        Pattern::Tuple { elements } => {
+            let mut spaces = vec![];
+            for element in elements {
+                spaces.push(lower(element, enums)?);
+            }
+            Ok(Space::Tuple(spaces))
+        }
+        Pattern::Variant { enum_name, variant, fields } => {
+            let variants = enums.get(&enum_name.lexeme).ok_or(format!("Unknown enum `{}`", enum_name.lexeme))?;
+            let index = variants.iter()
+                .position(|declared| declared.name.lexeme == variant.lexeme)
+                .ok_or(format!("No variant `{}` in enum `{}`", variant.lexeme, enum_name.lexeme))?;
+            let mut spaces = vec![];
+            match (&variants[index].fields, fields) {
+                (VariantFields::Struct { fields: declared }, VariantFields::Struct { fields }) => {
+                    // Fields of pattern can be written in any order
+                    for (name, _) in declared {
+                        match fields.iter().find(|(field, _)| field.lexeme == name.lexeme) {
+                            Some((_, field)) => spaces.push(lower(field, enums)?),
+                            None => spaces.push(Space::Any),
+                        }
+                    }
+                }
+                _ => {
+                    for field in fields.values() {
+                        spaces.push(lower(field, enums)?);
+                    }
+                }
+            }
+            let arities = variants.iter().map(|declared| declared.fields.values().len()).collect();
+            Ok(Space::Variant { index, arities, fields: spaces })
+        }
+    }
+}
+
+/// Returns true if `vector` matches some value which isn't matched by any of `rows`
+fn useful(rows: &[Vec<Space>], vector: &[Space]) -> bool {
+    let Some((head, rest)) = vector.split_first() else {
+        return rows.is_empty();
+    };
+    let column: Vec<&Space> = rows.iter().map(|row| &row[0]).collect();
+    let constructors = match head {
+        Space::Any => match complete_constructors(&column) {
+            Some(constructors) => constructors,
+            None => {
+                let default: Vec<Vec<Space>> = rows.iter()
+                    .filter(|row| row[0] == Space::Any)
+                    .map(|row| row[1..].to_vec())
+                    .collect();
+                return useful(&default, rest);
+            }
+        },
+        Space::Int { start, end, bounds } => split(*start, *end, *bounds, &column),
+        _ => vec![head.clone()],
+    };
+    constructors.iter().any(|constructor| {
+        let mut vector = fields(head, constructor);
+        vector.extend_from_slice(rest);
+        useful(&specialize(rows, constructor), &vector)
+    })
+}
+
+/// Returns constructors of column type if column covers all of them
+fn complete_constructors(column: &Vec<&Space>) -> Option<Vec<Space>> {
+    for space in column {
+        match space {
+            Space::Bool(_) => {
+                let covered = [true, false].iter().all(|value| column.contains(&&Space::Bool(*value)));
+                return covered.then(|| vec![Space::Bool(true), Space::Bool(false)]);
+            }
+            Space::Tuple(elements) => return Some(vec![Space::Tuple(vec![Space::Any; elements.len()])]),
+            Space::Variant { arities, .. } => {
+                let covered = (0..arities.len()).all(|index| {
+                    column.iter().any(|space| matches!(space, Space::Variant { index: other, .. } if *other == index))
+                });
+                let constructors = arities.iter().enumerate()
+                    .map(|(index, arity)| Space::Variant { index, arities: arities.clone(), fields: vec![Space::Any; *arity] })
+                    .collect();
+                return covered.then_some(constructors);
+            }
+            Space::Int { bounds: Some((min, max)), .. } => {
+                let pieces = split(*min, *max, Some((*min, *max)), column);
+                let covered = pieces.iter().all(|piece| column.iter().any(|space| contains(space, piece)));
+                return covered.then_some(pieces);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits `start..=end` on ranges of column, so every piece is inside or outside of each range
+fn split(start: i128, end: i128, bounds: Option<(i128, i128)>, column: &Vec<&Space>) -> Vec<Space> {
+    let mut starts = vec![start];
+    for space in column {
+        if let Space::Int { start: range_start, end: range_end, .. } = space {
+            // Range which ends at `i128::MAX` has nothing after it
+            for border in [Some(*range_start), range_end.checked_add(1)].into_iter().flatten() {
+                if border > start && border <= end {
+                    starts.push(border);
+                }
+            }
+        }
+    }
+    starts.sort();
+    starts.dedup();
+    starts.iter().enumerate()
+        .map(|(position, piece_start)| {
+            let piece_end = starts.get(position + 1).map_or(end, |next| next - 1);
+            Space::Int { start: *piece_start, end: piece_end, bounds }
+        })
+        .collect()
+}
+
+/// Returns true if values of `constructor` are matched by `space` without looking at subpatterns
+fn same_constructor(space: &Space, constructor: &Space) -> bool {
+    match (space, constructor) {
+        (Space::Any, _) | (Space::Tuple(_), Space::Tuple(_)) => true,
+        (Space::Variant { index, .. }, Space::Variant { index: other, .. }) => index == other,
+        _ => contains(space, constructor),
+    }
+}
+
+fn contains(space: &Space, piece: &Space) -> bool {
+    match (space, piece) {
+        (Space::Int { start, end, .. }, Space::Int { start: piece_start, end: piece_end, .. }) => {
+            start <= piece_start && piece_end <= end
+        }
+        _ => space == piece,
+    }
+}
+
+/// Returns subpatterns of `space` for `constructor`
+fn fields(space: &Space, constructor: &Space) -> Vec<Space> {
+    match (space, constructor) {
+        (Space::Tuple(elements), _) => elements.clone(),
+        (Space::Variant { fields, .. }, _) => fields.clone(),
+        (Space::Any, Space::Tuple(elements)) => vec![Space::Any; elements.len()],
+        (Space::Any, Space::Variant { fields, .. }) => vec![Space::Any; fields.len()],
+        _ => vec![],
+    }
+}
+
+/// Keeps rows which match `constructor`, their first column is replaced with subpatterns
+fn specialize(rows: &[Vec<Space>], constructor: &Space) -> Vec<Vec<Space>> {
+    rows.iter()
+        .filter(|row| same_constructor(&row[0], constructor))
+        .map(|row| {
+            let mut specialized = fields(&row[0], constructor);
+            specialized.extend_from_slice(&row[1..]);
+            specialized
+        })
+        .collect()
+}
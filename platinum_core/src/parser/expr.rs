@@ -1,5 +1,9 @@
+use std::fmt;
+
 use crate::lexer::token::{NumberType, Token, TokenType};
 
+use super::{captures::Capture, pattern::Pattern, stmt::{label_to_string, Argument, Statement, UseArgument, VariantFields}, types::TypeExpr};
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Type {
     String,
@@ -7,19 +11,67 @@ pub enum Type {
     Integer,
     Float,
     Bool,
-    Null
+    Array,
+    Map,
+    Tuple,
+    Range,
+    Function,
+    /// Value of any `enum`
+    Enum,
+    /// Instance of class, also used for interface types
+    Object,
+    Null,
+    /// Type which can't be known without resolving names (variables, fields)
+    Unknown
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Type::String => "String",
+            Type::Char => "char",
+            Type::Integer => "int",
+            Type::Float => "Float",
+            Type::Bool => "bool",
+            Type::Array => "array",
+            Type::Map => "map",
+            Type::Tuple => "tuple",
+            Type::Range => "range",
+            Type::Function => "function",
+            Type::Enum => "enum",
+            Type::Object => "object",
+            Type::Null => "null",
+            Type::Unknown => "unknown"
+        };
+        f.write_str(name)
+    }
 }
 
 impl Type {
-    fn to_string(&self) -> String {
-        match self {
-            Type::String => "String".to_string(),
-            Type::Char => "char".to_string(),
-            Type::Integer => "int".to_string(),
-            Type::Float => "Float".to_string(),
-            Type::Bool => "bool".to_string(),
-            Type::Null => "null".to_string()
-        }
+    /// Checks that two types can be used in the same place.
+    /// `Unknown` agrees with everything, it will be checked after name resolution.
+    pub fn agrees_with(&self, other: Type) -> bool {
+        *self == other || *self == Type::Unknown || other == Type::Unknown
+    }
+}
+
+/// `pattern if guard => body` in `match`
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expression>,
+    pub body: Box<Statement>,
+}
+
+impl fmt::Display for MatchArm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({}{} => {})",
+            self.pattern,
+            self.guard.as_ref().map(|guard| format!(" if {}", guard.to_string())).unwrap_or_default(),
+            self.body
+        )
     }
 }
 
@@ -27,9 +79,36 @@ impl Type {
 pub enum Expression {
     Binary { left: Box<Expression>, operator: Token, right: Box<Expression> },
     EqualtyComparison { left: Box<Expression>, operator: Token, right: Box<Expression> },
+    /// `a && b` or `a || b` on `bool` values, `right` is evaluated only if `left` doesn't decide the result
+    Logical { left: Box<Expression>, operator: Token, right: Box<Expression> },
     Ternary { result: Box<Expression>, true_expression: Box<Expression>, false_expression: Box<Expression> },
+    NullCoalescing { left: Box<Expression>, operator: Token, right: Box<Expression> },
     Unary { operator: Token, right: Box<Expression> },
     Grouping { expression: Box<Expression> },
+    Assign { target: Box<Expression>, operator: Token, value: Box<Expression> },
+    Increment { operator: Token, target: Box<Expression>, postfix: bool },
+    Get { object: Box<Expression>, name: Token, optional: bool },
+    /// `type_arguments` are written explicitly, `max::<i32>(a, b)`
+    Call { callee: Box<Expression>, type_arguments: Vec<TypeExpr>, arguments: Vec<UseArgument> },
+    Array { elements: Vec<Expression> },
+    ArrayRepeat { value: Box<Expression>, count: Box<Expression> },
+    Index { object: Box<Expression>, index: Box<Expression> },
+    Map { entries: Vec<(Expression, Expression)> },
+    Tuple { elements: Vec<Expression> },
+    TupleIndex { object: Box<Expression>, index: Token },
+    /// `a..b`, `a..=b`, `a..`, `..b` and `..`
+    Range { start: Option<Box<Expression>>, end: Option<Box<Expression>>, inclusive: bool },
+    /// `fun (x: i32) -> i32 { ... }` or `(x: i32) => x * 2`, arrow form without `->` has no `_type`
+    Lambda { arguments: Vec<Argument>, _type: Option<TypeExpr>, body: Box<Statement>, captures: Vec<Capture> },
+    Match { value: Box<Expression>, arms: Vec<MatchArm> },
+    /// `{ let a = b * 2; a + 1 }`, `value` is the last expression without `;`
+    Block { statements: Vec<Statement>, value: Option<Box<Expression>> },
+    /// `if (a > b) { a } else { b }`, `else_branch` is `Block` or another `If`
+    If { condition: Box<Expression>, then_branch: Box<Expression>, else_branch: Option<Box<Expression>> },
+    /// `'outer: loop { ... }` used as a value, it is given by `break`
+    Loop { label: Option<Token>, body: Box<Expression> },
+    /// `Shape::Circle(1.0)`, `Shape::Rect { w: 1.0, h: 2.0 }` or `Shape::Empty`
+    EnumVariant { enum_name: Token, variant: Token, fields: VariantFields<Expression> },
     Variable { name: Token },
     Literal { value: Token }
 }
@@ -53,6 +132,14 @@ impl Expression {
                     right.to_string()
                 )
             }
+            Expression::Logical { left, operator, right } => {
+                format!(
+                    "({} {} {})",
+                    operator.lexeme.clone(),
+                    left.to_string(),
+                    right.to_string()
+                )
+            }
             Expression::Ternary { result, true_expression, false_expression } => {
                 format!(
                     "(ternary {} ? {} : {})",
@@ -61,6 +148,14 @@ impl Expression {
                     (*false_expression).to_string()
                 )
             }
+            Expression::NullCoalescing { left, operator, right } => {
+                format!(
+                    "({} {} {})",
+                    operator.lexeme.clone(),
+                    (*left).to_string(),
+                    (*right).to_string()
+                )
+            }
             Expression::Grouping { expression } => {
                 format!("(group {})", (*expression).to_string())
             }
@@ -73,9 +168,151 @@ impl Expression {
             Expression::Unary { operator, right } => {
                 format!("({} {})", operator.lexeme.clone(), (*right).to_string())
             }
+            Expression::Assign { target, operator, value } => {
+                format!(
+                    "({} {} {})",
+                    operator.lexeme.clone(),
+                    (*target).to_string(),
+                    (*value).to_string()
+                )
+            }
+            Expression::Increment { operator, target, postfix } => {
+                if *postfix {
+                    format!("({} {})", (*target).to_string(), operator.lexeme.clone())
+                } else {
+                    format!("({} {})", operator.lexeme.clone(), (*target).to_string())
+                }
+            }
+            Expression::Get { object, name, optional } => {
+                format!(
+                    "(get{} {} {})",
+                    optional.then_some("?").unwrap_or(""),
+                    (*object).to_string(),
+                    name.lexeme.clone()
+                )
+            }
+            Expression::Call { callee, type_arguments, arguments } => {
+                let mut result = format!("(call {}", (*callee).to_string());
+                if !type_arguments.is_empty() {
+                    let type_arguments: Vec<String> = type_arguments.iter().map(|argument| argument.to_string()).collect();
+                    result.push_str(&format!("::<{}>", type_arguments.join(", ")));
+                }
+                for argument in arguments {
+                    result.push(' ');
+                    match argument {
+                        UseArgument::Expr { value } => result.push_str(&value.to_string()),
+                        UseArgument::Optional { name, value } => {
+                            result.push_str(&format!("{} = {}", name.lexeme, value.to_string()))
+                        }
+                    }
+                }
+                result.push(')');
+                result
+            }
+            Expression::Array { elements } => {
+                let elements: Vec<String> = elements.iter().map(|element| element.to_string()).collect();
+                format!("(array {})", elements.join(" "))
+            }
+            Expression::ArrayRepeat { value, count } => {
+                format!("(array {}; {})", (*value).to_string(), (*count).to_string())
+            }
+            Expression::Index { object, index } => {
+                format!("(index {} {})", (*object).to_string(), (*index).to_string())
+            }
+            Expression::Tuple { elements } => {
+                let elements: Vec<String> = elements.iter().map(|element| element.to_string()).collect();
+                format!("(tuple {})", elements.join(" "))
+            }
+            Expression::TupleIndex { object, index } => {
+                format!("(get {} {})", (*object).to_string(), index.lexeme.clone())
+            }
+            Expression::Map { entries } => {
+                let entries: Vec<String> = entries.iter()
+                    .map(|(key, value)| format!("{}: {}", key.to_string(), value.to_string()))
+                    .collect();
+                format!("(map {})", entries.join(", "))
+            }
+            Expression::Range { start, end, inclusive } => {
+                format!(
+                    "(range {}{}{})",
+                    start.as_ref().map(|start| start.to_string()).unwrap_or_default(),
+                    inclusive.then_some("..=").unwrap_or(".."),
+                    end.as_ref().map(|end| end.to_string()).unwrap_or_default()
+                )
+            }
+            Expression::Lambda { arguments, _type, body, captures } => {
+                let arguments: Vec<String> = arguments.iter().map(|argument| argument.to_string()).collect();
+                let captures: Vec<String> = captures.iter().map(|capture| capture.to_string()).collect();
+                format!(
+                    "(lambda ({}){}{} {})",
+                    arguments.join(", "),
+                    _type.as_ref().map(|_type| format!(" -> {}", _type)).unwrap_or_default(),
+                    if captures.is_empty() { String::new() } else { format!(" [{}]", captures.join(", ")) },
+                    body
+                )
+            }
+            Expression::Match { value, arms } => {
+                let arms: Vec<String> = arms.iter().map(|arm| arm.to_string()).collect();
+                format!("(match {} {})", (*value).to_string(), arms.join(" "))
+            }
+            Expression::Block { statements, value } => {
+                let mut result = "(block \n".to_string();
+                for statement in statements {
+                    result.push_str(&statement.to_string());
+                    result.push('\n');
+                }
+                if let Some(value) = value {
+                    result.push_str(&value.to_string());
+                    result.push('\n');
+                }
+                result.push(')');
+                result
+            }
+            Expression::If { condition, then_branch, else_branch } => {
+                match else_branch {
+                    Some(else_branch) => {
+                        format!("(if ({}) {} else {})", condition.to_string(), then_branch.to_string(), else_branch.to_string())
+                    }
+                    None => format!("(if ({}) {})", condition.to_string(), then_branch.to_string()),
+                }
+            }
+            Expression::Loop { label, body } => format!("({}loop {})", label_to_string(label), body.to_string()),
+            Expression::EnumVariant { enum_name, variant, fields } => {
+                format!(
+                    "(variant {}::{}{})",
+                    enum_name.lexeme,
+                    variant.lexeme,
+                    fields.to_string(|value| value.to_string())
+                )
+            }
+        }
+    }
+
+    /// Returns true if `(` after expression starts a call, not a new grouping
+    pub fn is_callable(&self) -> bool {
+        match self {
+            Expression::Variable { .. } |
+            Expression::Get { .. } |
+            Expression::TupleIndex { .. } |
+            Expression::Call { .. } |
+            Expression::Lambda { .. } => true,
+            // `(fun () { ... })()`
+            Expression::Grouping { expression } => matches!(**expression, Expression::Lambda { .. }),
+            _ => false
         }
     }
 
+    /// Returns true if `[` after expression starts an index, not a new array literal.
+    /// Expressions which end with block can be statements without `;`, so `[` after them isn't an index
+    pub fn is_indexable(&self) -> bool {
+        !matches!(self, Expression::Block { .. } | Expression::If { .. } | Expression::Loop { .. } | Expression::Match { .. })
+    }
+
+    /// Returns true if expression can be on the left side of assigment
+    pub fn is_place(&self) -> bool {
+        matches!(self, Expression::Variable { .. } | Expression::Get { .. } | Expression::TupleIndex { .. } | Expression::Index { .. })
+    }
+
     pub fn check_and_get_type(&self) -> Result<Type, String> {
         match self {
             Expression::Binary { left, operator, right } => {
@@ -86,7 +323,7 @@ impl Expression {
                         if match_token(operator.token_type.clone(), TokenType::Plus) {
                             return Ok(Type::String);
                         }
-                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                     }
                     (Type::Integer, Type::Integer) => {
                         if match_tokens(
@@ -100,7 +337,7 @@ impl Expression {
                             ) {
                             return Ok(Type::Integer);
                         }
-                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                     }
                     (Type::Integer, Type::Float) |
                     (Type::Float, Type::Integer) |
@@ -114,8 +351,10 @@ impl Expression {
                             ) {
                             return Ok(Type::Float);
                         }
-                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                     }
+                    (Type::Unknown, _) |
+                    (_, Type::Unknown) => Ok(Type::Unknown),
                     _ => Err("Unexpected binary operator".to_string())
                 }
             }
@@ -123,23 +362,25 @@ impl Expression {
                 let left_result = (*left).check_and_get_type()?;
                 let right_result = (*right).check_and_get_type()?;
                 match (left_result, right_result) {
+                    (Type::Unknown, _) |
+                    (_, Type::Unknown) => Ok(Type::Bool),
                     (Type::String, Type::String) => {
                         if match_tokens(operator.clone(), vec![TokenType::EqualEqual, TokenType::BangEqual]) {
                             return Ok(Type::Bool);
                         }
-                        return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                     }
                     (Type::Integer, Type::Integer) => {
                         if match_tokens(
                             operator.clone(),
                             vec![
                                 TokenType::EqualEqual, TokenType::BangEqual, TokenType::LessEqual,
-                                TokenType::Less, TokenType::GreaterEqual, TokenType::Greater, TokenType::QuestionQuestion
+                                TokenType::Less, TokenType::GreaterEqual, TokenType::Greater
                                 ]
                             ) {
                             return Ok(Type::Bool);
                         }
-                        return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                     }
                     (Type::Integer, Type::Float) |
                     (Type::Float, Type::Integer) |
@@ -148,25 +389,23 @@ impl Expression {
                             operator.clone(),
                             vec![
                                 TokenType::EqualEqual, TokenType::BangEqual, TokenType::LessEqual,
-                                TokenType::Less, TokenType::GreaterEqual, TokenType::Greater, TokenType::QuestionQuestion,
-                                TokenType::Or, TokenType::And
+                                TokenType::Less, TokenType::GreaterEqual, TokenType::Greater
                                 ]
                             ) {
                             return Ok(Type::Bool);
                         }
-                        return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                     }
                     (Type::Bool, Type::Bool) => {
                         if match_tokens(
                             operator.clone(),
                             vec![
-                                TokenType::EqualEqual, TokenType::BangEqual,
-                                TokenType::Or, TokenType::And
+                                TokenType::EqualEqual, TokenType::BangEqual
                                 ]
                             ) {
                             return Ok(Type::Bool);
                         }
-                        return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                     }
                     (_, Type::Null) |
                     (Type::Null, _) => {
@@ -178,21 +417,32 @@ impl Expression {
                             ) {
                             return Ok(Type::Bool);
                         }
-                        return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                     }
                     _ => return Err("Unexpected binary operator".to_string())
                 }
             }
+            Expression::Logical { left, operator, right } => {
+                for operand in [left, right] {
+                    let operand_result = operand.check_and_get_type()?;
+                    if !operand_result.agrees_with(Type::Bool) {
+                        return Err(format!("Operands of `{}` should be `{}`, found `{}`", operator.lexeme, Type::Bool, operand_result));
+                    }
+                }
+                Ok(Type::Bool)
+            }
             Expression::Ternary { result, true_expression, false_expression } => {
                 let res = (*result).check_and_get_type()?;
-                if res != Type::Bool {
+                if !res.agrees_with(Type::Bool) {
                     return Err("Condition should be bool value".to_string());
                 }
 
                 let true_result = (*true_expression).check_and_get_type()?;
                 let false_result = (*false_expression).check_and_get_type()?;
-                if true_result != false_result {
+                if !true_result.agrees_with(false_result) {
                     Err("Expression #1 and expression #2 should return the same type!".to_string())
+                } else if true_result == Type::Unknown {
+                    Ok(false_result)
                 } else {
                     Ok(true_result)
                 }
@@ -201,11 +451,12 @@ impl Expression {
             Expression::Unary { operator, right } => {
                 let result = (*right).check_and_get_type()?;
                 match operator.token_type {
+                    _ if result == Type::Unknown => Ok(Type::Unknown),
                     TokenType::Bang => {
                         if result == Type::Bool {
                             return Ok(Type::Bool);
                         }
-                        return Err(format!("You can't use `{}` with unary operator `!`", result.to_string()));
+                        Err(format!("You can't use `{}` with unary operator `{}`", result, operator.lexeme))
                     }
                     TokenType::Minus => {
                         if result == Type::Integer {
@@ -213,13 +464,260 @@ impl Expression {
                         } else if result == Type::Float {
                             return Ok(Type::Float);
                         }
-                        return Err(format!("You can't use `{}` with unary operator `!`", result.to_string()));
+                        Err(format!("You can't use `{}` with unary operator `{}`", result, operator.lexeme))
                     }
                     _ => Err("Unexpected binary operator".to_string())
                 }
             }
+            Expression::Assign { target, operator, value } => {
+                let target_result = (*target).check_and_get_type()?;
+                if operator.token_type == TokenType::QuestionQuestionEqual {
+                    return Expression::NullCoalescing {
+                        left: target.clone(),
+                        operator: operator.clone(),
+                        right: value.clone()
+                    }.check_and_get_type();
+                }
+                let value_result = match compound_operator(&operator.token_type) {
+                    Some(token_type) => {
+                        let mut lexeme = operator.lexeme.clone();
+                        lexeme.pop();
+                        Expression::Binary {
+                            left: target.clone(),
+                            operator: Token::new(token_type, lexeme, operator.possition),
+                            right: value.clone()
+                        }.check_and_get_type()?
+                    }
+                    None => (*value).check_and_get_type()?
+                };
+                if !target_result.agrees_with(value_result) {
+                    return Err(format!("Can't assign `{}` value to `{}`", value_result, target_result));
+                }
+                if target_result == Type::Unknown {
+                    Ok(value_result)
+                } else {
+                    Ok(target_result)
+                }
+            }
+            Expression::Increment { operator, target, .. } => {
+                let result = (*target).check_and_get_type()?;
+                match result {
+                    Type::Integer | Type::Float | Type::Unknown => Ok(result),
+                    _ => Err(format!("You can't use `{}` with operator `{}`", result, operator.lexeme))
+                }
+            }
+            Expression::NullCoalescing { left, operator, right } => {
+                let left_result = (*left).check_and_get_type()?;
+                let right_result = (*right).check_and_get_type()?;
+                match (left_result, right_result) {
+                    (Type::Null, _) | (Type::Unknown, _) => Ok(right_result),
+                    (_, Type::Null) => Ok(left_result),
+                    _ => {
+                        if left_result.agrees_with(right_result) {
+                            return Ok(left_result);
+                        }
+                        Err(format!(
+                            "Can't use operator `{}` with `{}` and `{}` types",
+                            operator.lexeme,
+                            left_result,
+                            right_result
+                        ))
+                    }
+                }
+            }
+            Expression::Array { elements } => {
+                let mut element_type = Type::Unknown;
+                for element in elements {
+                    let result = element.check_and_get_type()?;
+                    if !element_type.agrees_with(result) {
+                        return Err(format!(
+                            "Array elements should have the same type, found `{}` and `{}`",
+                            element_type,
+                            result
+                        ));
+                    }
+                    if element_type == Type::Unknown {
+                        element_type = result;
+                    }
+                }
+                Ok(Type::Array)
+            }
+            Expression::ArrayRepeat { value, count } => {
+                let _ = (*value).check_and_get_type()?;
+                let count_result = (*count).check_and_get_type()?;
+                if !count_result.agrees_with(Type::Integer) {
+                    return Err(format!("Array length should be `{}`, found `{}`", Type::Integer, count_result));
+                }
+                Ok(Type::Array)
+            }
+            Expression::Map { entries } => {
+                let _ = map_entries_types(entries)?;
+                Ok(Type::Map)
+            }
+            Expression::Tuple { elements } => {
+                for element in elements {
+                    let _ = element.check_and_get_type()?;
+                }
+                Ok(Type::Tuple)
+            }
+            Expression::TupleIndex { object, index } => {
+                let object_result = (*object).check_and_get_type()?;
+                match object_result {
+                    Type::Tuple => {
+                        if let Expression::Tuple { elements } = &**object {
+                            return match elements.get(tuple_index(index)?) {
+                                Some(element) => element.check_and_get_type(),
+                                None => Err(format!("Tuple with {} elements has no field `{}`", elements.len(), index.lexeme))
+                            };
+                        }
+                        Ok(Type::Unknown)
+                    }
+                    Type::Unknown => Ok(Type::Unknown),
+                    _ => Err(format!("Can't get field `{}` of `{}` type", index.lexeme, object_result))
+                }
+            }
+            Expression::Index { object, index } => {
+                let object_result = (*object).check_and_get_type()?;
+                let index_result = (*index).check_and_get_type()?;
+                if object_result == Type::Map {
+                    if let Expression::Map { entries } = &**object {
+                        let (key_result, value_result) = map_entries_types(entries)?;
+                        if !key_result.agrees_with(index_result) {
+                            return Err(format!("Map key should be `{}`, found `{}`", key_result, index_result));
+                        }
+                        return Ok(value_result);
+                    }
+                    return Ok(Type::Unknown);
+                }
+                if index_result == Type::Range {
+                    return match object_result {
+                        Type::String | Type::Array | Type::Unknown => Ok(object_result),
+                        _ => Err(format!("Can't slice value of `{}` type", object_result))
+                    };
+                }
+                if object_result == Type::Unknown {
+                    // Could be a map with any keys
+                    return Ok(Type::Unknown);
+                }
+                if !index_result.agrees_with(Type::Integer) {
+                    return Err(format!("Index should be `{}`, found `{}`", Type::Integer, index_result));
+                }
+                match object_result {
+                    Type::String => Ok(Type::Char),
+                    Type::Array => Ok(Type::Unknown),
+                    _ => Err(format!("Can't index value of `{}` type", object_result))
+                }
+            }
+            Expression::Range { start, end, .. } => {
+                let mut bounds_type = Type::Unknown;
+                for bound in [start, end].into_iter().flatten() {
+                    let bound_result = bound.check_and_get_type()?;
+                    if !matches!(bound_result, Type::Integer | Type::Char | Type::Unknown) {
+                        return Err(format!("Range bounds should be `{}` or `{}`, found `{}`", Type::Integer, Type::Char, bound_result));
+                    }
+                    if !bounds_type.agrees_with(bound_result) {
+                        return Err(format!("Range bounds should have the same type, found `{}` and `{}`", bounds_type, bound_result));
+                    }
+                    bounds_type = bound_result;
+                }
+                Ok(Type::Range)
+            }
             Expression::Grouping { expression } => (*expression).check_and_get_type(),
-            Expression::Variable { .. } => todo!(),
+            Expression::Get { object, name, .. } => {
+                let object_result = (*object).check_and_get_type()?;
+                if object_result == Type::Range && !RANGE_METHODS.contains(&name.lexeme.as_str()) {
+                    return Err(format!("No method `{}` on `{}` type", name.lexeme, Type::Range));
+                }
+                Ok(Type::Unknown)
+            }
+            Expression::Call { callee, arguments, .. } => {
+                let _ = (*callee).check_and_get_type()?;
+                let mut argument_types = vec![];
+                for argument in arguments {
+                    match argument {
+                        UseArgument::Expr { value } |
+                        UseArgument::Optional { value, .. } => argument_types.push(value.check_and_get_type()?)
+                    }
+                }
+                if let Expression::Get { object, name, .. } = &**callee {
+                    if (*object).check_and_get_type()? == Type::Range {
+                        return range_method_type(name, &argument_types);
+                    }
+                }
+                Ok(Type::Unknown)
+            }
+            Expression::Lambda { .. } => Ok(Type::Function),
+            Expression::EnumVariant { fields, .. } => {
+                for value in fields.values() {
+                    let _ = value.check_and_get_type()?;
+                }
+                Ok(Type::Enum)
+            }
+            Expression::Match { value, arms } => {
+                let value_result = (*value).check_and_get_type()?;
+                let mut result = Type::Unknown;
+                for (position, arm) in arms.iter().enumerate() {
+                    arm.pattern.check_value(value)?;
+                    let pattern_result = arm.pattern.get_type()?;
+                    if !value_result.agrees_with(pattern_result) {
+                        return Err(format!("Pattern `{}` should match `{}`, found `{}`", arm.pattern, value_result, pattern_result));
+                    }
+                    for other in &arms[..position] {
+                        other.pattern.agrees_with(&arm.pattern)?;
+                    }
+                    if let Some(guard) = &arm.guard {
+                        let guard_result = guard.check_and_get_type()?;
+                        if !Type::Bool.agrees_with(guard_result) {
+                            return Err(format!("Match guard should be `{}`, found `{}`", Type::Bool, guard_result));
+                        }
+                    }
+                    if let Statement::Assigment { expression } = &*arm.body {
+                        let arm_result = expression.check_and_get_type()?;
+                        // `null` arm makes result nullable
+                        if arm_result == Type::Null {
+                            continue;
+                        }
+                        if !result.agrees_with(arm_result) {
+                            return Err(format!("Match arms have different types `{}` and `{}`", result, arm_result));
+                        }
+                        if arm_result != Type::Unknown {
+                            result = arm_result;
+                        }
+                    }
+                }
+                Ok(result)
+            }
+            Expression::Block { value, .. } => {
+                match value {
+                    Some(value) => value.check_and_get_type(),
+                    None => Ok(Type::Unknown),
+                }
+            }
+            Expression::If { condition, then_branch, else_branch } => {
+                let condition_result = condition.check_and_get_type()?;
+                if !condition_result.agrees_with(Type::Bool) {
+                    return Err(format!("Condition of `if` should be `{}`, found `{}`", Type::Bool, condition_result));
+                }
+                let then_result = then_branch.check_and_get_type()?;
+                let else_result = match else_branch {
+                    Some(else_branch) => else_branch.check_and_get_type()?,
+                    None => return Ok(Type::Unknown),
+                };
+                // `null` branch makes result nullable
+                if then_result == Type::Null {
+                    return Ok(else_result);
+                }
+                if else_result == Type::Null {
+                    return Ok(then_result);
+                }
+                if !then_result.agrees_with(else_result) {
+                    return Err(format!("`if` and `else` have incompatible types `{}` and `{}`", then_result, else_result));
+                }
+                Ok(if then_result == Type::Unknown { else_result } else { then_result })
+            }
+            // Value is given by `break`
+            Expression::Loop { .. } => Ok(Type::Unknown),
+            Expression::Variable { .. } => Ok(Type::Unknown),
             Expression::Literal { value } => {
                 match value.token_type {
                     TokenType::StringT {..} => Ok(Type::String),
@@ -278,7 +776,7 @@ impl Expression {
                             left_value.push_str(&right_value);
                             return Ok(Expression::Literal { value: Token::new(TokenType::StringT { value: left_value.clone() }, left_value.clone(), operator.possition)});
                         }
-                        return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()));
+                        Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                     }
                     (Type::Integer, Type::Integer) => {
                         let left_value = match *left.clone() {
@@ -303,14 +801,14 @@ impl Expression {
                             TokenType::Plus => left_value + right_value,
                             TokenType::Minus => left_value - right_value,
                             TokenType::Star => left_value * right_value,
-                            TokenType::Slash => left_value / right_value,
-                            TokenType::Persent => left_value % right_value,
+                            TokenType::Slash => left_value.checked_div(right_value).ok_or(format!("Division by zero at possition {:?}", operator.possition))?,
+                            TokenType::Persent => left_value.checked_rem(right_value).ok_or(format!("Division by zero at possition {:?}", operator.possition))?,
                             TokenType::LessLess => left_value << right_value,
                             TokenType::GreaterGreater => left_value >> right_value,
                             TokenType::Bar => left_value | right_value,
                             TokenType::Caret => left_value ^ right_value,
                             TokenType::Ampersant => left_value & right_value,
-                            _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                            _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                         };
                         return Ok(Expression::Literal { value: Token::new(TokenType::Int { value: value.to_string(), num_type: NumberType::UntypedInt }, value.to_string(), operator.possition) })
                     }
@@ -343,10 +841,12 @@ impl Expression {
                             TokenType::Star => left_value * right_value,
                             TokenType::Slash => left_value / right_value,
                             TokenType::Persent => left_value % right_value,
-                            _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                            _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                         };
                         return Ok(Expression::Literal { value: Token::new(TokenType::Float { value: value.to_string(), num_type: NumberType::UntypedFloat }, value.to_string(), operator.possition) })
                     }
+                    (Type::Unknown, _) |
+                    (_, Type::Unknown) => Ok(Expression::Binary { left: left.clone(), operator: operator.clone(), right: right.clone() }),
                     _ => return Err("Unexpected binary operator".to_string())
                 }
             }
@@ -390,20 +890,13 @@ impl Expression {
                         }
                     } else {
                         match (left_token.token_type, right_token.token_type) {
-                            (TokenType::StringT { value: value_left }, TokenType::StringT { value: value_right }) => {
-                                match operator.token_type.clone() {
-                                    TokenType::QuestionQuestion => todo!(),
-                                    _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
-                                }
-                            }
                             (TokenType::Char { value: value_left }, TokenType::Char { value: value_right }) => {
                                 match operator.token_type.clone() {
                                     TokenType::LessEqual => value_left <= value_right,
                                     TokenType::Less => value_left < value_right,
                                     TokenType::GreaterEqual => value_left >= value_right,
                                     TokenType::Greater => value_left > value_right,
-                                    TokenType::QuestionQuestion => todo!(),
-                                    _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                                    _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                                 }
                             }
                             (TokenType::Int { value: value_left, .. }, TokenType::Int { value: value_right, .. }) => {
@@ -412,8 +905,7 @@ impl Expression {
                                     TokenType::Less => parse_i128(&value_left)? < parse_i128(&value_right)?,
                                     TokenType::GreaterEqual => parse_i128(&value_left)? >= parse_i128(&value_right)?,
                                     TokenType::Greater => parse_i128(&value_left)? > parse_i128(&value_right)?,
-                                    TokenType::QuestionQuestion => todo!(),
-                                    _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                                    _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                                 }
                             }
                             (TokenType::Int { value: value_left, .. }, TokenType::Float { value: value_right, .. }) |
@@ -424,34 +916,41 @@ impl Expression {
                                     TokenType::Less => parse_f64(&value_left) < parse_f64(&value_right),
                                     TokenType::GreaterEqual => parse_f64(&value_left) >= parse_f64(&value_right),
                                     TokenType::Greater => parse_f64(&value_left) > parse_f64(&value_right),
-                                    TokenType::QuestionQuestion => todo!(),
-                                    _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                                    _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                                 }
                             }
-                            (TokenType::BoolT { value: value_left }, TokenType::BoolT { value: value_right }) => {
-                                match operator.token_type.clone() {
-                                    TokenType::Or => value_left || value_right,
-                                    TokenType::And => value_left && value_right,
-                                    TokenType::QuestionQuestion => todo!(),
-                                    _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
-                                }
-                            }
-                            _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result.to_string()))
+                            _ => return Err(format!("Can't use operator `{}` with `{}` type", operator.lexeme, left_result))
                         }
                     }
                 };
                 return Ok(Expression::Literal { value: Token::new(TokenType::BoolT { value: result }, result.to_string(), operator.possition) })
             }
+            Expression::Logical { left, operator, right } => {
+                *left = Box::from(left.optimize_expression()?);
+                let decided = match **left {
+                    Expression::Literal { value: Token { token_type: TokenType::BoolT { value }, .. } } => Some(value),
+                    _ => None,
+                };
+                match (decided, &operator.token_type) {
+                    // `false && f()` and `true || f()` are folded without `f()`, it would never be called
+                    (Some(false), TokenType::And) | (Some(true), TokenType::Or) => Ok(*left.clone()),
+                    (Some(_), _) => right.optimize_expression(),
+                    (None, _) => {
+                        *right = Box::from(right.optimize_expression()?);
+                        Ok(Expression::Logical { left: left.clone(), operator: operator.clone(), right: right.clone() })
+                    }
+                }
+            }
             Expression::Ternary { result, true_expression, false_expression } => {
                 let result_type = (*result).check_and_get_type()?;
                 let true_expression_result = (*true_expression).check_and_get_type()?;
                 let false_expression_result = (*false_expression).check_and_get_type()?;
 
-                if result_type != Type::Bool {
-                    return Err(format!("Condition expression should have `{}` value", Type::Bool.to_string()));
+                if !result_type.agrees_with(Type::Bool) {
+                    return Err(format!("Condition expression should have `{}` value", Type::Bool));
                 }
                 
-                if true_expression_result != false_expression_result {
+                if !true_expression_result.agrees_with(false_expression_result) {
                     return Err("Left expression and right expression should return the same type".to_string());
                 }
 
@@ -546,16 +1045,300 @@ impl Expression {
                             _ => return Ok(Expression::Unary { operator: operator.clone(), right: right.clone() })
                         }
                     }
+                    Type::Unknown => Ok(Expression::Unary { operator: operator.clone(), right: right.clone() }),
                     _ => return Err("Unexpected AST error".to_string())
                 }
             }
             Expression::Grouping { expression } => {
                 return (*expression).clone().optimize_expression();
             }
+            Expression::Assign { target, operator, value } => {
+                *value = Box::from((*value).optimize_expression()?);
+                Ok(Expression::Assign { target: target.clone(), operator: operator.clone(), value: value.clone() })
+            }
+            Expression::Increment { .. } => Ok(self.clone()),
+            Expression::NullCoalescing { left, operator, right } => {
+                *left = Box::from((*left).optimize_expression()?);
+                *right = Box::from((*right).optimize_expression()?);
+                match *left.clone() {
+                    Expression::Literal { value } => {
+                        if value.token_type == TokenType::Null {
+                            return Ok(*right.clone());
+                        }
+                        Ok(*left.clone())
+                    }
+                    _ => Ok(Expression::NullCoalescing { left: left.clone(), operator: operator.clone(), right: right.clone() })
+                }
+            }
+            Expression::Get { object, name, optional } => {
+                *object = Box::from((*object).optimize_expression()?);
+                if *optional && is_null_literal(object) {
+                    return Ok(*object.clone());
+                }
+                Ok(Expression::Get { object: object.clone(), name: name.clone(), optional: *optional })
+            }
+            Expression::Call { callee, type_arguments, arguments } => {
+                // `null?.method()` is null, the call is never made
+                if let Expression::Get { object, optional: true, .. } = &mut **callee {
+                    let object = (*object).optimize_expression()?;
+                    if is_null_literal(&object) {
+                        return Ok(object);
+                    }
+                }
+                *callee = Box::from((*callee).optimize_expression()?);
+                for argument in arguments.iter_mut() {
+                    match argument {
+                        UseArgument::Expr { value } |
+                        UseArgument::Optional { value, .. } => *value = value.optimize_expression()?
+                    }
+                }
+                if let Expression::Get { object, name, .. } = &**callee {
+                    if let (Expression::Range { .. }, "step", Some(UseArgument::Expr { value })) = (&**object, name.lexeme.as_str(), arguments.first()) {
+                        if let Some(step) = int_literal(value)? {
+                            if step <= 0 {
+                                return Err(format!("Range step should be positive, found {}", step));
+                            }
+                        }
+                    }
+                }
+                Ok(Expression::Call { callee: callee.clone(), type_arguments: type_arguments.clone(), arguments: arguments.clone() })
+            }
+            Expression::Array { elements } => {
+                for element in elements.iter_mut() {
+                    *element = element.optimize_expression()?;
+                }
+                Ok(Expression::Array { elements: elements.clone() })
+            }
+            Expression::ArrayRepeat { value, count } => {
+                *value = Box::from((*value).optimize_expression()?);
+                *count = Box::from((*count).optimize_expression()?);
+                if let Some(count) = int_literal(count)? {
+                    if count < 0 {
+                        return Err(format!("Array length can't be negative: {}", count));
+                    }
+                }
+                Ok(Expression::ArrayRepeat { value: value.clone(), count: count.clone() })
+            }
+            Expression::Index { object, index } => {
+                *object = Box::from((*object).optimize_expression()?);
+                *index = Box::from((*index).optimize_expression()?);
+                if let (Expression::Array { elements }, Some(position)) = (&**object, int_literal(index)?) {
+                    if position < 0 || position as usize >= elements.len() {
+                        return Err(format!("Index out of bounds: the length is {} but the index is {}", elements.len(), position));
+                    }
+                    return Ok(elements[position as usize].clone());
+                }
+                if let (Expression::Map { entries }, Expression::Literal { value: key }) = (&**object, &**index) {
+                    for (entry_key, entry_value) in entries {
+                        if let Expression::Literal { value } = entry_key {
+                            if same_literal(&value.token_type, &key.token_type)? {
+                                return Ok(entry_value.clone());
+                            }
+                        }
+                    }
+                    if entries.iter().all(|(entry_key, _)| matches!(entry_key, Expression::Literal { .. })) {
+                        return Err(format!("Key `{}` isn't found in map", key.lexeme));
+                    }
+                }
+                Ok(Expression::Index { object: object.clone(), index: index.clone() })
+            }
+            Expression::Tuple { elements } => {
+                for element in elements.iter_mut() {
+                    *element = element.optimize_expression()?;
+                }
+                Ok(Expression::Tuple { elements: elements.clone() })
+            }
+            Expression::TupleIndex { object, index } => {
+                *object = Box::from((*object).optimize_expression()?);
+                if let Expression::Tuple { elements } = &**object {
+                    return match elements.get(tuple_index(index)?) {
+                        Some(element) => Ok(element.clone()),
+                        None => Err(format!("Tuple with {} elements has no field `{}`", elements.len(), index.lexeme))
+                    };
+                }
+                Ok(Expression::TupleIndex { object: object.clone(), index: index.clone() })
+            }
+            Expression::Map { entries } => {
+                for (key, value) in entries.iter_mut() {
+                    *key = key.optimize_expression()?;
+                    *value = value.optimize_expression()?;
+                }
+                for (position, (key, _)) in entries.iter().enumerate() {
+                    if let Expression::Literal { value } = key {
+                        for (other, _) in entries.iter().skip(position + 1) {
+                            if let Expression::Literal { value: other } = other {
+                                if same_literal(&value.token_type, &other.token_type)? {
+                                    return Err(format!("Duplicate key `{}` in map", value.lexeme));
+                                }
+                            }
+                        }
+                    }
+                }
+                Ok(Expression::Map { entries: entries.clone() })
+            }
+            Expression::Range { start, end, inclusive } => {
+                if let Some(start) = start {
+                    *start = Box::from(start.optimize_expression()?);
+                }
+                if let Some(end) = end {
+                    *end = Box::from(end.optimize_expression()?);
+                }
+                Ok(Expression::Range { start: start.clone(), end: end.clone(), inclusive: *inclusive })
+            }
+            // Statements in body are folded by `Fold` pass on their own
+            Expression::Lambda { .. } |
+            Expression::Block { .. } |
+            Expression::Loop { .. } => Ok(self.clone()),
+            Expression::If { condition, then_branch, else_branch } => {
+                *condition = Box::from(condition.optimize_expression()?);
+                if let Expression::Literal { value: Token { token_type: TokenType::BoolT { value }, .. } } = **condition {
+                    // Only taken branch is left, without `else` nothing is done
+                    return Ok(match (value, else_branch) {
+                        (true, _) => *then_branch.clone(),
+                        (false, Some(else_branch)) => *else_branch.clone(),
+                        (false, None) => Expression::Block { statements: vec![], value: None },
+                    });
+                }
+                Ok(Expression::If { condition: condition.clone(), then_branch: then_branch.clone(), else_branch: else_branch.clone() })
+            }
+            Expression::Match { value, arms } => {
+                *value = Box::from(value.optimize_expression()?);
+                for arm in arms.iter_mut() {
+                    if let Some(guard) = &mut arm.guard {
+                        *guard = guard.optimize_expression()?;
+                    }
+                }
+                Ok(Expression::Match { value: value.clone(), arms: arms.clone() })
+            }
+            Expression::EnumVariant { enum_name, variant, fields } => {
+                let fields = match fields {
+                    VariantFields::Unit => VariantFields::Unit,
+                    VariantFields::Tuple { values } => {
+                        let mut optimized = vec![];
+                        for value in values.iter_mut() {
+                            optimized.push(value.optimize_expression()?);
+                        }
+                        VariantFields::Tuple { values: optimized }
+                    }
+                    VariantFields::Struct { fields } => {
+                        let mut optimized = vec![];
+                        for (name, value) in fields.iter_mut() {
+                            optimized.push((name.clone(), value.optimize_expression()?));
+                        }
+                        VariantFields::Struct { fields: optimized }
+                    }
+                };
+                Ok(Expression::EnumVariant { enum_name: enum_name.clone(), variant: variant.clone(), fields })
+            }
             Expression::Variable { .. } => return Ok(self.clone()),
             Expression::Literal { .. } => return Ok(self.clone()),
         }
-        todo!()
+    }
+}
+
+const RANGE_METHODS: [&str; 2] = ["step", "rev"];
+
+/// Returns type of `(0..10).step(2)` and `(0..10).rev()`
+fn range_method_type(name: &Token, argument_types: &Vec<Type>) -> Result<Type, String> {
+    let expected_arguments = match name.lexeme.as_str() {
+        "step" => vec![Type::Integer],
+        "rev" => vec![],
+        _ => return Err(format!("No method `{}` on `{}` type", name.lexeme, Type::Range))
+    };
+    if expected_arguments.len() != argument_types.len() {
+        return Err(format!(
+            "Method `{}` takes {} arguments but {} were supplied",
+            name.lexeme,
+            expected_arguments.len(),
+            argument_types.len()
+        ));
+    }
+    for (expected, argument) in expected_arguments.iter().zip(argument_types) {
+        if !expected.agrees_with(*argument) {
+            return Err(format!("Method `{}` expects `{}`, found `{}`", name.lexeme, expected, argument));
+        }
+    }
+    Ok(Type::Range)
+}
+
+/// Returns value of integer literal, `None` if expression isn't integer literal
+fn int_literal(expression: &Expression) -> Result<Option<i128>, String> {
+    match expression {
+        Expression::Literal { value } => {
+            match &value.token_type {
+                TokenType::Int { value, .. } => Ok(Some(parse_i128(value)?)),
+                _ => Ok(None)
+            }
+        }
+        _ => Ok(None)
+    }
+}
+
+/// Checks that all keys and all values of map literal have the same types
+/// and returns them, `Unknown` for empty map
+fn map_entries_types(entries: &Vec<(Expression, Expression)>) -> Result<(Type, Type), String> {
+    let mut key_type = Type::Unknown;
+    let mut value_type = Type::Unknown;
+    for (key, value) in entries {
+        let key_result = key.check_and_get_type()?;
+        let value_result = value.check_and_get_type()?;
+        if !key_type.agrees_with(key_result) {
+            return Err(format!("Map keys should have the same type, found `{}` and `{}`", key_type, key_result));
+        }
+        if !value_type.agrees_with(value_result) {
+            return Err(format!("Map values should have the same type, found `{}` and `{}`", value_type, value_result));
+        }
+        if key_type == Type::Unknown {
+            key_type = key_result;
+        }
+        if value_type == Type::Unknown {
+            value_type = value_result;
+        }
+    }
+    Ok((key_type, value_type))
+}
+
+fn same_literal(left: &TokenType, right: &TokenType) -> Result<bool, String> {
+    match (left, right) {
+        (TokenType::Int { value: value_left, .. }, TokenType::Int { value: value_right, .. }) => {
+            Ok(parse_i128(value_left)? == parse_i128(value_right)?)
+        }
+        (TokenType::Float { value: value_left, .. }, TokenType::Float { value: value_right, .. }) => {
+            Ok(parse_f64(value_left)? == parse_f64(value_right)?)
+        }
+        _ => Ok(left == right)
+    }
+}
+
+/// Returns position of tuple field (`t.0`)
+fn tuple_index(index: &Token) -> Result<usize, String> {
+    match index.lexeme.parse::<usize>() {
+        Ok(position) => Ok(position),
+        Err(_) => Err(format!("Invalid tuple field `{}`", index.lexeme))
+    }
+}
+
+fn is_null_literal(expression: &Expression) -> bool {
+    match expression {
+        Expression::Literal { value } => value.token_type == TokenType::Null,
+        _ => false
+    }
+}
+
+/// Returns binary operator of compound assigment (`+=` -> `+`)
+fn compound_operator(token_type: &TokenType) -> Option<TokenType> {
+    match token_type {
+        TokenType::PlusEqual => Some(TokenType::Plus),
+        TokenType::MinusEqual => Some(TokenType::Minus),
+        TokenType::StarEqual => Some(TokenType::Star),
+        TokenType::SlashEqual => Some(TokenType::Slash),
+        TokenType::PersentEqual => Some(TokenType::Persent),
+        TokenType::LessLessEqual => Some(TokenType::LessLess),
+        TokenType::GreaterGreaterEqual => Some(TokenType::GreaterGreater),
+        TokenType::AmpersantEqual => Some(TokenType::Ampersant),
+        TokenType::BarEqual => Some(TokenType::Bar),
+        TokenType::CaretEqual => Some(TokenType::Caret),
+        _ => None
     }
 }
 
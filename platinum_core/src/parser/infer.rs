@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use crate::lexer::token::{NumberType, Token};
+
+use super::{expr::Type, stmt::{Argument, Visibility}, types::{TypeExpr, TypeParameter}};
+
+/// Type used by inference, unlike `Type` it keeps names and type parameters.
+/// `to_checked` is the only conversion between them
+#[derive(Debug, PartialEq, Clone)]
+pub enum Ty {
+    /// `i32`, `String`, `Shape`, also `{integer}` and `{float}` for literals without suffix
+    Named(String),
+    /// `List<i32>`
+    Generic(String, Vec<Ty>),
+    Nullable(Box<Ty>),
+    Array(Box<Ty>),
+    Map(Box<Ty>, Box<Ty>),
+    Tuple(Vec<Ty>),
+    Function(Vec<Ty>, Box<Ty>),
+    /// Type parameter which is inferred from arguments
+    Var(String),
+    /// Type which can't be known without resolving names
+    Unknown,
+}
+
+pub const INTEGER_LITERAL: &str = "{integer}";
+pub const FLOAT_LITERAL: &str = "{float}";
+
+const INTEGER_TYPES: [&str; 12] = ["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"];
+const FLOAT_TYPES: [&str; 2] = ["f32", "f64"];
+
+/// Interfaces which are implemented by builtin types
+pub const BUILTIN_BOUNDS: [&str; 3] = ["Eq", "Ord", "Num"];
+
+impl Ty {
+    /// Converts annotation, names from `parameters` become type variables
+    pub fn from_type(_type: &TypeExpr, parameters: &Vec<String>) -> Ty {
+        let convert = |_type: &TypeExpr| Ty::from_type(_type, parameters);
+        match _type {
+            TypeExpr::Named { name } => {
+                if parameters.contains(&name.lexeme) {
+                    Ty::Var(name.lexeme.clone())
+                } else {
+                    Ty::Named(name.lexeme.clone())
+                }
+            }
+            TypeExpr::Generic { name, arguments } => Ty::Generic(name.lexeme.clone(), arguments.iter().map(convert).collect()),
+            TypeExpr::Nullable { inner } => Ty::Nullable(Box::from(convert(inner))),
+            TypeExpr::Array { element, .. } => Ty::Array(Box::from(convert(element))),
+            TypeExpr::Map { key, value } => Ty::Map(Box::from(convert(key)), Box::from(convert(value))),
+            TypeExpr::Tuple { elements } => Ty::Tuple(elements.iter().map(convert).collect()),
+            TypeExpr::Function { arguments, returned } => {
+                Ty::Function(arguments.iter().map(convert).collect(), Box::from(convert(returned)))
+            }
+        }
+    }
+
+    /// Returns type of number literal, `{integer}` if it has no suffix
+    pub fn from_number(num_type: NumberType) -> Ty {
+        let name = match num_type {
+            NumberType::I8 => "i8",
+            NumberType::I16 => "i16",
+            NumberType::I32 => "i32",
+            NumberType::I64 => "i64",
+            NumberType::I128 => "i128",
+            NumberType::ISize => "isize",
+            NumberType::U8 => "u8",
+            NumberType::U16 => "u16",
+            NumberType::U32 => "u32",
+            NumberType::U64 => "u64",
+            NumberType::U128 => "u128",
+            NumberType::USize => "usize",
+            NumberType::F32 => "f32",
+            NumberType::F64 => "f64",
+            NumberType::UntypedInt => INTEGER_LITERAL,
+            NumberType::UntypedFloat => FLOAT_LITERAL,
+        };
+        Ty::Named(name.to_string())
+    }
+
+    /// Converts to type used by `check_and_get_type`, names which aren't builtin types are `Unknown`
+    pub fn to_checked(&self) -> Type {
+        match self {
+            Ty::Named(name) if INTEGER_TYPES.contains(&name.as_str()) || name == INTEGER_LITERAL => Type::Integer,
+            Ty::Named(name) if FLOAT_TYPES.contains(&name.as_str()) || name == FLOAT_LITERAL => Type::Float,
+            Ty::Named(name) => {
+                match name.as_str() {
+                    "bool" => Type::Bool,
+                    "char" => Type::Char,
+                    "String" => Type::String,
+                    "null" => Type::Null,
+                    _ => Type::Unknown,
+                }
+            }
+            Ty::Array(_) => Type::Array,
+            Ty::Map(..) => Type::Map,
+            Ty::Tuple(_) => Type::Tuple,
+            Ty::Function(..) => Type::Function,
+            Ty::Generic(..) | Ty::Nullable(_) | Ty::Var(_) | Ty::Unknown => Type::Unknown,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        let names = |types: &Vec<Ty>| types.iter().map(|_type| _type.name()).collect::<Vec<String>>().join(", ");
+        match self {
+            Ty::Named(name) | Ty::Var(name) => name.clone(),
+            Ty::Generic(name, arguments) => format!("{}<{}>", name, names(arguments)),
+            Ty::Nullable(inner) => format!("{}?", inner.name()),
+            Ty::Array(element) => format!("[{}]", element.name()),
+            Ty::Map(key, value) => format!("#{{{}: {}}}", key.name(), value.name()),
+            Ty::Tuple(elements) => format!("({})", names(elements)),
+            Ty::Function(arguments, returned) => format!("fun({}) -> {}", names(arguments), returned.name()),
+            Ty::Unknown => "unknown".to_string(),
+        }
+    }
+
+    /// Replaces `{integer}` with `i32` and `{float}` with `f64` when there is nothing else to infer them from
+    pub fn with_default_literals(&self) -> Ty {
+        let default_all = |types: &Vec<Ty>| types.iter().map(|_type| _type.with_default_literals()).collect();
+        match self {
+            Ty::Named(name) if name == INTEGER_LITERAL => Ty::Named("i32".to_string()),
+            Ty::Named(name) if name == FLOAT_LITERAL => Ty::Named("f64".to_string()),
+            Ty::Generic(name, arguments) => Ty::Generic(name.clone(), default_all(arguments)),
+            Ty::Nullable(inner) => Ty::Nullable(Box::from(inner.with_default_literals())),
+            Ty::Array(element) => Ty::Array(Box::from(element.with_default_literals())),
+            Ty::Map(key, value) => Ty::Map(Box::from(key.with_default_literals()), Box::from(value.with_default_literals())),
+            Ty::Tuple(elements) => Ty::Tuple(default_all(elements)),
+            Ty::Function(arguments, returned) => Ty::Function(default_all(arguments), Box::from(returned.with_default_literals())),
+            Ty::Named(_) | Ty::Var(_) | Ty::Unknown => self.clone(),
+        }
+    }
+
+    /// Returns true for builtin numbers, chars, strings and bools
+    pub fn is_primitive(&self) -> bool {
+        match self {
+            Ty::Named(name) => self.is_number() || ["bool", "char", "String"].contains(&name.as_str()),
+            _ => false,
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        match self {
+            Ty::Named(name) => {
+                INTEGER_TYPES.contains(&name.as_str()) || FLOAT_TYPES.contains(&name.as_str()) ||
+                name == INTEGER_LITERAL || name == FLOAT_LITERAL
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Declared function which calls are checked against
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub generics: Vec<TypeParameter>,
+    pub arguments: Vec<Argument>,
+    pub _type: TypeExpr,
+}
+
+impl Signature {
+    pub fn parameter_names(&self) -> Vec<String> {
+        self.generics.iter().map(|parameter| parameter.name.lexeme.clone()).collect()
+    }
+}
+
+/// Declared class which annotations and accesses to members are checked against
+#[derive(Debug, Clone)]
+pub struct ClassSignature {
+    pub generics: Vec<TypeParameter>,
+    pub interfaces: Vec<Token>,
+    pub members: Vec<(Token, Visibility)>,
+    /// Class is declared in another module
+    pub imported: bool,
+}
+
+/// Types inferred for type variables
+#[derive(Debug, Default)]
+pub struct Substitution {
+    types: HashMap<String, Ty>,
+}
+
+impl Substitution {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bind(&mut self, name: &str, _type: Ty) {
+        self.types.insert(name.to_string(), _type);
+    }
+
+    /// Replaces known type variables in `_type`
+    pub fn apply(&self, _type: &Ty) -> Ty {
+        let apply_all = |types: &Vec<Ty>| types.iter().map(|_type| self.apply(_type)).collect();
+        match _type {
+            Ty::Var(name) => match self.types.get(name) {
+                Some(bound) => self.apply(bound),
+                None => _type.clone(),
+            },
+            Ty::Generic(name, arguments) => Ty::Generic(name.clone(), apply_all(arguments)),
+            Ty::Nullable(inner) => Ty::Nullable(Box::from(self.apply(inner))),
+            Ty::Array(element) => Ty::Array(Box::from(self.apply(element))),
+            Ty::Map(key, value) => Ty::Map(Box::from(self.apply(key)), Box::from(self.apply(value))),
+            Ty::Tuple(elements) => Ty::Tuple(apply_all(elements)),
+            Ty::Function(arguments, returned) => Ty::Function(apply_all(arguments), Box::from(self.apply(returned))),
+            Ty::Named(_) | Ty::Unknown => _type.clone(),
+        }
+    }
+
+    /// Makes `left` and `right` the same type by binding type variables
+    pub fn unify(&mut self, left: &Ty, right: &Ty) -> Result<(), String> {
+        let left = self.apply(left);
+        let right = self.apply(right);
+        match (&left, &right) {
+            (Ty::Unknown, _) | (_, Ty::Unknown) => Ok(()),
+            (Ty::Var(a), Ty::Var(b)) if a == b => Ok(()),
+            (Ty::Var(name), other) | (other, Ty::Var(name)) => {
+                self.bind(name, other.clone());
+                Ok(())
+            }
+            (Ty::Named(a), Ty::Named(b)) => {
+                if a == b || literal_fits(a, b) || literal_fits(b, a) {
+                    return Ok(());
+                }
+                Err(mismatch(&left, &right))
+            }
+            (Ty::Nullable(_), Ty::Named(name)) | (Ty::Named(name), Ty::Nullable(_)) if name == "null" => Ok(()),
+            (Ty::Nullable(a), Ty::Nullable(b)) => self.unify(a, b),
+            (Ty::Nullable(inner), other) | (other, Ty::Nullable(inner)) => self.unify(inner, other),
+            (Ty::Generic(a, a_arguments), Ty::Generic(b, b_arguments)) if a == b => self.unify_all(a_arguments, b_arguments, &left, &right),
+            (Ty::Array(a), Ty::Array(b)) => self.unify(a, b),
+            (Ty::Map(a_key, a_value), Ty::Map(b_key, b_value)) => {
+                self.unify(a_key, b_key)?;
+                self.unify(a_value, b_value)
+            }
+            (Ty::Tuple(a), Ty::Tuple(b)) => self.unify_all(a, b, &left, &right),
+            (Ty::Function(a_arguments, a_returned), Ty::Function(b_arguments, b_returned)) => {
+                self.unify_all(a_arguments, b_arguments, &left, &right)?;
+                self.unify(a_returned, b_returned)
+            }
+            _ => Err(mismatch(&left, &right)),
+        }
+    }
+
+    fn unify_all(&mut self, left: &[Ty], right: &[Ty], left_type: &Ty, right_type: &Ty) -> Result<(), String> {
+        if left.len() != right.len() {
+            return Err(mismatch(left_type, right_type));
+        }
+        for (left, right) in left.iter().zip(right) {
+            self.unify(left, right)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces `{integer}` of type variable with concrete type when it becomes known
+    pub fn refine(&mut self, name: &str, _type: &Ty) {
+        if let Some(Ty::Named(bound)) = self.types.get(name) {
+            if (bound == INTEGER_LITERAL || bound == FLOAT_LITERAL) && matches!(_type, Ty::Named(other) if literal_fits(bound, other)) {
+                self.bind(name, _type.clone());
+            }
+        }
+    }
+}
+
+/// Returns type of value which can come from two branches, `{integer}` gives way to a concrete type
+pub fn join(left: &Ty, right: &Ty) -> Result<Ty, String> {
+    Substitution::new().unify(left, right)?;
+    let null = Ty::Named("null".to_string());
+    Ok(match (left, right) {
+        (Ty::Unknown, _) => Ty::Unknown,
+        (_, Ty::Unknown) => Ty::Unknown,
+        (Ty::Nullable(_), _) if *right == null => left.clone(),
+        (_, Ty::Nullable(_)) if *left == null => right.clone(),
+        _ if *left == null => Ty::Nullable(Box::from(right.clone())),
+        _ if *right == null => Ty::Nullable(Box::from(left.clone())),
+        (Ty::Named(name), _) if name == INTEGER_LITERAL || name == FLOAT_LITERAL => right.clone(),
+        _ => left.clone(),
+    })
+}
+
+/// Returns true if literal without suffix can have type `name`
+fn literal_fits(literal: &str, name: &str) -> bool {
+    (literal == INTEGER_LITERAL && INTEGER_TYPES.contains(&name)) ||
+    (literal == FLOAT_LITERAL && FLOAT_TYPES.contains(&name))
+}
+
+fn mismatch(left: &Ty, right: &Ty) -> String {
+    format!("Mismatched types `{}` and `{}`", left.name(), right.name())
+}
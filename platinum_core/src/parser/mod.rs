@@ -1,3 +1,10 @@
 pub mod parser;
 pub mod stmt;
 pub mod expr;
+pub mod types;
+pub mod pattern;
+pub mod captures;
+pub mod exhaustiveness;
+pub mod infer;
+pub mod constants;
+pub mod operators;
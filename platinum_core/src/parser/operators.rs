@@ -0,0 +1,167 @@
+use crate::lexer::token::TokenType::{self, *};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Associativity {
+    Left,
+    Right,
+    /// `a..b..c` is not allowed
+    None,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Fixity {
+    Prefix,
+    Infix,
+    Postfix,
+}
+
+/// Expression which is built by operator
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Node {
+    Assign,
+    Ternary,
+    NullCoalescing,
+    Range,
+    Logical,
+    Binary,
+    Comparison,
+    Unary,
+    Increment,
+    Field,
+    Call,
+    Index,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Operator {
+    pub token_type: TokenType,
+    pub symbol: &'static str,
+    /// Operators with bigger precedence bind tighter
+    pub precedence: u8,
+    pub associativity: Associativity,
+    pub fixity: Fixity,
+    pub node: Node,
+}
+
+impl Operator {
+    /// Lowest precedence which operand of operator can have
+    pub fn operand_precedence(&self) -> u8 {
+        match self.associativity {
+            Associativity::Right => self.precedence,
+            Associativity::Left | Associativity::None => self.precedence + 1,
+        }
+    }
+}
+
+pub const ASSIGMENT: u8 = 1;
+pub const TERNARY: u8 = 2;
+pub const NULL_COALESCING: u8 = 3;
+pub const RANGE: u8 = 4;
+pub const LOGICAL_OR: u8 = 5;
+pub const LOGICAL_AND: u8 = 6;
+pub const BITWISE_OR: u8 = 7;
+pub const BITWISE_XOR: u8 = 8;
+pub const BITWISE_AND: u8 = 9;
+pub const EQUALTY: u8 = 10;
+pub const COMPARISON: u8 = 11;
+pub const SHIFT: u8 = 12;
+pub const TERM: u8 = 13;
+pub const FACTOR: u8 = 14;
+pub const UNARY: u8 = 15;
+pub const POSTFIX: u8 = 16;
+
+macro_rules! operator {
+    ($token_type:expr, $symbol:expr, $precedence:expr, $associativity:ident, $fixity:ident, $node:ident) => {
+        Operator {
+            token_type: $token_type,
+            symbol: $symbol,
+            precedence: $precedence,
+            associativity: Associativity::$associativity,
+            fixity: Fixity::$fixity,
+            node: Node::$node,
+        }
+    };
+}
+
+/// All operators of expressions, parser uses only this table to decide how operators bind
+pub const OPERATORS: [Operator; 46] = [
+    operator!(Equal, "=", ASSIGMENT, Right, Infix, Assign),
+    operator!(PlusEqual, "+=", ASSIGMENT, Right, Infix, Assign),
+    operator!(MinusEqual, "-=", ASSIGMENT, Right, Infix, Assign),
+    operator!(StarEqual, "*=", ASSIGMENT, Right, Infix, Assign),
+    operator!(SlashEqual, "/=", ASSIGMENT, Right, Infix, Assign),
+    operator!(PersentEqual, "%=", ASSIGMENT, Right, Infix, Assign),
+    operator!(LessLessEqual, "<<=", ASSIGMENT, Right, Infix, Assign),
+    operator!(GreaterGreaterEqual, ">>=", ASSIGMENT, Right, Infix, Assign),
+    operator!(AmpersantEqual, "&=", ASSIGMENT, Right, Infix, Assign),
+    operator!(BarEqual, "|=", ASSIGMENT, Right, Infix, Assign),
+    operator!(CaretEqual, "^=", ASSIGMENT, Right, Infix, Assign),
+    operator!(QuestionQuestionEqual, "??=", ASSIGMENT, Right, Infix, Assign),
+    operator!(Question, "? :", TERNARY, Right, Infix, Ternary),
+    operator!(QuestionQuestion, "??", NULL_COALESCING, Right, Infix, NullCoalescing),
+    operator!(DotDot, "..", RANGE, None, Prefix, Range),
+    operator!(DotDotEqual, "..=", RANGE, None, Prefix, Range),
+    operator!(DotDot, "..", RANGE, None, Infix, Range),
+    operator!(DotDotEqual, "..=", RANGE, None, Infix, Range),
+    operator!(Or, "||", LOGICAL_OR, Left, Infix, Logical),
+    operator!(And, "&&", LOGICAL_AND, Left, Infix, Logical),
+    operator!(Bar, "|", BITWISE_OR, Left, Infix, Binary),
+    operator!(Caret, "^", BITWISE_XOR, Left, Infix, Binary),
+    operator!(Ampersant, "&", BITWISE_AND, Left, Infix, Binary),
+    operator!(EqualEqual, "==", EQUALTY, Left, Infix, Comparison),
+    operator!(BangEqual, "!=", EQUALTY, Left, Infix, Comparison),
+    operator!(Less, "<", COMPARISON, Left, Infix, Comparison),
+    operator!(LessEqual, "<=", COMPARISON, Left, Infix, Comparison),
+    operator!(Greater, ">", COMPARISON, Left, Infix, Comparison),
+    operator!(GreaterEqual, ">=", COMPARISON, Left, Infix, Comparison),
+    operator!(LessLess, "<<", SHIFT, Left, Infix, Binary),
+    operator!(GreaterGreater, ">>", SHIFT, Left, Infix, Binary),
+    operator!(Plus, "+", TERM, Left, Infix, Binary),
+    operator!(Minus, "-", TERM, Left, Infix, Binary),
+    operator!(Star, "*", FACTOR, Left, Infix, Binary),
+    operator!(Slash, "/", FACTOR, Left, Infix, Binary),
+    operator!(Persent, "%", FACTOR, Left, Infix, Binary),
+    operator!(Bang, "!", UNARY, Right, Prefix, Unary),
+    operator!(Minus, "-", UNARY, Right, Prefix, Unary),
+    operator!(PlusPlus, "++", UNARY, Right, Prefix, Increment),
+    operator!(MinusMinus, "--", UNARY, Right, Prefix, Increment),
+    operator!(Dot, ".", POSTFIX, Left, Postfix, Field),
+    operator!(QuestionDot, "?.", POSTFIX, Left, Postfix, Field),
+    operator!(LeftParen, "()", POSTFIX, Left, Postfix, Call),
+    operator!(LeftBrace, "[]", POSTFIX, Left, Postfix, Index),
+    operator!(PlusPlus, "++", POSTFIX, Left, Postfix, Increment),
+    operator!(MinusMinus, "--", POSTFIX, Left, Postfix, Increment),
+];
+
+/// Operator which can start expression
+pub fn prefix(token_type: &TokenType) -> Option<&'static Operator> {
+    OPERATORS.iter().find(|operator| operator.fixity == Fixity::Prefix && &operator.token_type == token_type)
+}
+
+/// Operator which can follow operand
+pub fn infix(token_type: &TokenType) -> Option<&'static Operator> {
+    OPERATORS.iter().find(|operator| operator.fixity != Fixity::Prefix && &operator.token_type == token_type)
+}
+
+/// Operators from tightest to loosest binding, one line per precedence and fixity
+pub fn precedence_table() -> String {
+    let mut lines: Vec<String> = vec![];
+    let mut groups: Vec<(u8, Fixity, Associativity, Vec<&str>)> = vec![];
+    for operator in OPERATORS.iter() {
+        match groups.iter_mut().find(|(precedence, fixity, ..)| *precedence == operator.precedence && *fixity == operator.fixity) {
+            Some((.., symbols)) => {
+                if !symbols.contains(&operator.symbol) {
+                    symbols.push(operator.symbol);
+                }
+            }
+            None => groups.push((operator.precedence, operator.fixity, operator.associativity, vec![operator.symbol])),
+        }
+    }
+    groups.sort_by_key(|group| std::cmp::Reverse(group.0));
+    for (precedence, fixity, associativity, symbols) in groups {
+        let fixity = format!("{:?}", fixity).to_lowercase();
+        let associativity = format!("{:?}", associativity).to_lowercase();
+        lines.push(format!("{:>2}  {:<7}  {:<5}  {}", precedence, fixity, associativity, symbols.join(" ")).trim_end().to_string());
+    }
+    lines.join("\n")
+}
@@ -1,11 +1,40 @@
-use crate::{lexer::token::{Token, TokenType::{self, *}}, parser::stmt::{Argument, UseArgument}};
+use std::collections::{HashMap, HashSet};
 
-use super::{expr::Expression, stmt::Statement};
+use crate::{lexer::token::{NumberType, Token, TokenType::{self, *}}, parser::stmt::{Argument, UseArgument}};
+
+use super::{captures::find_captures, constants::{is_constant, substitute}, expr::{Expression, MatchArm}, infer::{Ty, BUILTIN_BOUNDS}, operators::{self, Associativity, Fixity, Node, Operator, ASSIGMENT}, pattern::Pattern, stmt::{Attribute, InterfaceMethod, Item, Program, Statement, Variant, VariantFields, Visibility, INLINE_MODES, LINTS}, types::{TypeExpr, TypeParameter}};
 
 pub struct Parser {
     file_path: String,
     tokens: Vec<Token>,
     current: usize,
+    /// Variants of enums declared before current token
+    enums: HashMap<String, Vec<Variant>>,
+    interfaces: HashMap<String, Vec<InterfaceMethod>>,
+    /// Names of classes declared before current token
+    classes: HashSet<String>,
+    /// Top level enums and interfaces found before parsing file which weren't reached yet,
+    /// they can be used before they are declared
+    hoisted: HashSet<String>,
+    /// Names of variables in scopes which contain current token, the first scope is top level of file
+    variables: Vec<HashSet<String>>,
+    /// Values of constants with other constants substituted
+    constants: HashMap<String, Expression>,
+    /// Statics with their mutability and initial values
+    statics: HashMap<String, (bool, Expression)>,
+    /// Loops which bodies contain current token, the innermost is the last
+    loops: Vec<LoopContext>,
+}
+
+/// Loop which `break` and `continue` are checked against
+struct LoopContext {
+    label: Option<String>,
+    /// Only `loop` can be left with a value
+    gives_value: bool,
+    /// Some `break` was parsed with a value
+    value_break: bool,
+    /// Some `break` was parsed without a value
+    empty_break: bool,
 }
 
 impl Parser {
@@ -14,49 +43,244 @@ impl Parser {
             file_path: file_path.to_string(),
             tokens: tokens,
             current: 0,
+            enums: HashMap::new(),
+            interfaces: HashMap::new(),
+            classes: HashSet::new(),
+            hoisted: HashSet::new(),
+            variables: vec![HashSet::new()],
+            constants: HashMap::new(),
+            statics: HashMap::new(),
+            loops: vec![],
         }
     }
 
-    pub fn parse(&mut self) -> Result<Vec<Statement>, String> {
-        let mut stmts = vec![];
+    pub fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    /// Parses all tokens as items of file, reports all errors at once.
+    /// Expressions are left as they were written and their types aren't checked, only names
+    /// of constants are replaced with their values where value should be known at compile time
+    pub fn parse_program(&mut self) -> Result<Program, String> {
         let mut errors = vec![];
-        
-        while !self.is_at_end() {
-            match self.statement() {
-                Ok(stmt) => stmts.push(stmt),
+        let mut stmts = vec![];
+        let header_start = self.current;
+        while self.look_tokens(0, vec![Module, Import]) {
+            let start = self.current;
+            match self.header_declaration(start == header_start) {
+                Ok(declaration) => stmts.push(declaration),
                 Err(msg) => {
-                    errors.push(msg);
-                    self.synchronize();
+                    errors.push(format!("{}: {}", self.file_path, msg));
+                    self.synchronize(start);
                 }
-
             }
-            self.advance();
         }
+        self.hoist_declarations();
+        stmts.extend(self.items(&mut errors));
 
-        if errors.len() > 0 {
+        if !errors.is_empty() {
             let mut joined = "".to_string();
             for msg in errors.iter() {
-                joined.push_str(&msg);
-                joined.push_str("\n");
+                joined.push_str(msg);
+                joined.push('\n');
             }
             return Err(joined);
         }
-        Ok(stmts)
+        Ok(Program { items: stmts.into_iter().map(Item::new).collect() })
     }
 
+    fn items(&mut self, errors: &mut Vec<String>) -> Vec<Statement> {
+        let mut items = vec![];
+        while !self.is_at_end() {
+            let start = self.current;
+            match self.statement() {
+                Ok(stmt) => items.push(stmt),
+                Err(msg) => {
+                    errors.push(format!("{}: {}", self.file_path, msg));
+                    self.synchronize(start);
+                    // Failed declaration can leave its scopes open
+                    self.variables.truncate(1);
+                    self.loops.clear();
+                }
+            }
+        }
+        items
+    }
+
+    /// Finds top level enums and interfaces in one pass over the rest of tokens and parses only them,
+    /// so they can be used in bodies of declarations before them. Broken declarations are skipped,
+    /// their errors are reported when they are parsed in order
+    fn hoist_declarations(&mut self) {
+        let start = self.current;
+        let variables = self.variables.clone();
+        let mut depth = 0usize;
+        let mut position = start;
+        while position < self.tokens.len() {
+            match self.tokens[position].token_type {
+                LeftCurBrace => depth += 1,
+                RightCurBrace => depth = depth.saturating_sub(1),
+                Enum | Interface if depth == 0 => {
+                    let is_enum = self.tokens[position].token_type == Enum;
+                    let name = self.tokens.get(position + 1).map(|token| token.lexeme.clone()).unwrap_or_default();
+                    let declared = if is_enum { self.enums.contains_key(&name) } else { self.interfaces.contains_key(&name) };
+                    if !declared {
+                        self.current = position + 1;
+                        let hoisted = if is_enum {
+                            self.enum_statement(Visibility::Private)
+                        } else {
+                            self.interface_statement(Visibility::Private)
+                        };
+                        // Default methods of interface could leave their scopes open
+                        self.variables = variables.clone();
+                        self.loops.clear();
+                        if hoisted.is_ok() {
+                            self.hoisted.insert(name);
+                            position = self.current;
+                            continue;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            position += 1;
+        }
+        self.current = start;
+    }
+
+    /// Parses single expression, there should be no tokens after it
+    pub fn parse_expression(&mut self) -> Result<Expression, String> {
+        let expression = self.expression().and_then(|expression| self.end_of_input("expression").map(|_| expression));
+        expression.map_err(|msg| format!("{}: {}", self.file_path, msg))
+    }
+
+    /// Parses single statement, there should be no tokens after it
+    pub fn parse_statement(&mut self) -> Result<Statement, String> {
+        let statement = self.statement().and_then(|statement| self.end_of_input("statement").map(|_| statement));
+        statement.map_err(|msg| format!("{}: {}", self.file_path, msg))
+    }
+
+    /// Parses single type, there should be no tokens after it
+    pub fn parse_type(&mut self) -> Result<TypeExpr, String> {
+        let _type = self.type_expr("Type expected").and_then(|_type| self.end_of_input("type").map(|_| _type));
+        _type.map_err(|msg| format!("{}: {}", self.file_path, msg))
+    }
+
+    fn end_of_input(&self, parsed: &str) -> Result<(), String> {
+        if !self.is_at_end() {
+            let token = self.peek();
+            return Err(format!("Unexpected `{}` after {} at possition {:?}", token.lexeme, parsed, token.possition));
+        }
+        Ok(())
+    }
+
+    /// Parses `module` and `import` declarations at the beginning of file,
+    /// they can be parsed before the rest of file to load imported modules
+    pub fn parse_header(&mut self) -> Result<Vec<Statement>, String> {
+        let mut declarations = vec![];
+        while self.look_tokens(0, vec![Module, Import]) {
+            let declaration = self.header_declaration(declarations.is_empty());
+            declarations.push(declaration.map_err(|msg| format!("{}: {}", self.file_path, msg))?);
+        }
+        Ok(declarations)
+    }
+
+    /// Makes declarations of `module` with given names visible in this file
+    pub fn import_declarations(&mut self, module: &Parser, names: &[String]) {
+        copy_declarations(&module.enums, &mut self.enums, names);
+        copy_declarations(&module.interfaces, &mut self.interfaces, names);
+        copy_declarations(&module.constants, &mut self.constants, names);
+        copy_declarations(&module.statics, &mut self.statics, names);
+        for name in names {
+            if module.classes.contains(name) {
+                self.classes.insert(name.clone());
+            }
+            if module.variables[0].contains(name) {
+                self.variables[0].insert(name.clone());
+            }
+        }
+    }
+
+    fn header_declaration(&mut self, is_first: bool) -> Result<Statement, String> {
+        if self.match_token(Module) {
+            if !is_first {
+                return Err("`module` declaration should be the first in file".to_string());
+            }
+            let path = self.module_path()?;
+            let _ = self.consume(Semicolon, "`;` expected after module declaration")?;
+            return Ok(Statement::Module { path });
+        }
+        let _ = self.consume(Import, "`import` expected")?;
+        let mut names: Vec<Token> = vec![];
+        if self.match_token(LeftCurBrace) {
+            loop {
+                let name = self.consume(Identifier { value: String::new() }, "Imported name expected")?;
+                if names.iter().any(|other| other.lexeme == name.lexeme) {
+                    return Err(format!("`{}` is imported more than once", name.lexeme));
+                }
+                names.push(name);
+                if !self.match_token(Comma) {
+                    break;
+                }
+            }
+            let _ = self.consume(RightCurBrace, "`}` expected after imported names")?;
+            let _ = self.consume(From, "`from` expected after imported names")?;
+        }
+        let path = self.module_path()?;
+        let _ = self.consume(Semicolon, "`;` expected after import declaration")?;
+        Ok(Statement::Import { path, names })
+    }
+
+    /// Parses path of module, `math.vector`
+    fn module_path(&mut self) -> Result<Vec<Token>, String> {
+        let mut path = vec![self.consume(Identifier { value: String::new() }, "Module name expected")?];
+        while self.match_token(Dot) {
+            path.push(self.consume(Identifier { value: String::new() }, "Module name expected after `.`")?);
+        }
+        Ok(path)
+    }
 
     fn statement(&mut self) -> Result<Statement, String> {
+        if self.look_token(0, Hash) && self.look_token(1, LeftBrace) {
+            let attrs = self.attributes()?;
+            let statement = self.statement()?;
+            return self.attach_attributes(attrs, statement);
+        }
+        if self.look_token(0, Label { value: String::new() }) && self.look_token(1, Colon) {
+            return self.labeled_statement();
+        }
+        if self.look_token(0, Semicolon) {
+            return Err("Statement expected, found `;`".to_string());
+        }
+        if self.look_tokens(0, vec![Module, Import]) {
+            return Err("`module` and `import` declarations should be at the beginning of file".to_string());
+        }
+        if self.look_tokens(0, vec![Pub, Private]) {
+            return self.visible_declaration();
+        }
         if self.match_token(LeftCurBrace) {
             return self.block_statement();
         }
         if self.match_token(Let) {
-            return self.let_statement();
+            return self.let_statement(Visibility::Module);
         }
-        if self.match_token(Fun) {
-            return self.func_statement();
+        if self.match_tokens(vec![Const, Static]) {
+            return self.global_statement(Visibility::Module);
+        }
+        if self.look_token(0, Fun) && self.look_token(1, Identifier { value: String::new() }) {
+            self.advance();
+            return self.func_statement(Visibility::Module);
+        }
+        if self.match_token(Enum) {
+            return self.enum_statement(Visibility::Module);
+        }
+        if self.match_token(Interface) {
+            return self.interface_statement(Visibility::Module);
+        }
+        if self.match_token(Class) {
+            return self.class_statement(Visibility::Module);
         }
         if self.match_token(For) {
-            return self.for_statement();
+            return self.for_statement(None);
         }
         if self.match_token(Range) {
             return self.range_statement();
@@ -74,77 +298,498 @@ impl Parser {
             return self.if_else_statement();
         }
         if self.match_token(While) {
-            return self.while_statement();
+            return self.while_statement(None);
         }
         if self.match_token(DoWhile) {
-            return self.do_while_statement();
+            return self.do_while_statement(None);
         }
         if self.match_token(Loop) {
-            return self.loop_statement();
+            return self.loop_statement(None);
         }
-        println!("{:?}", self.peek());
         if self.look_token(0, Identifier { value: String::new() }) && self.look_token(1, LeftParen) {
             return self.func_use_statement(); // todo
         }
         self.assigment_statement()
     }
 
+    /// Parses `#[name]` and `#[name(arguments)]` written before declaration, empty if there are none
+    fn attributes(&mut self) -> Result<Vec<Attribute>, String> {
+        let mut attrs: Vec<Attribute> = vec![];
+        while self.look_token(0, Hash) && self.look_token(1, LeftBrace) {
+            self.advance();
+            self.advance();
+            let name = self.consume(Identifier { value: String::new() }, "Attribute name expected after `#[`")?;
+            let mut arguments = vec![];
+            if self.match_token(LeftParen) {
+                while !self.match_token(RightParen) {
+                    if !arguments.is_empty() {
+                        let _ = self.consume(Comma, "`,` or `)` expected in attribute arguments")?;
+                    }
+                    let argument = self.advance();
+                    match argument.token_type {
+                        Identifier { .. } | StringT { .. } | Char { .. } | Int { .. } | Float { .. } | BoolT { .. } => arguments.push(argument),
+                        _ => return Err(format!("Argument of attribute `{}` should be identifier or literal", name.lexeme)),
+                    }
+                }
+            }
+            let _ = self.consume(RightBrace, "`]` expected after attribute")?;
+            if attrs.iter().any(|attr| attr.name.lexeme == name.lexeme) {
+                return Err(format!("Attribute `{}` is used more than once", name.lexeme));
+            }
+            attrs.push(Attribute { name, arguments });
+        }
+        Ok(attrs)
+    }
+
+    /// Checks names and arguments of attributes and stores them in declaration
+    fn attach_attributes(&self, attrs: Vec<Attribute>, mut statement: Statement) -> Result<Statement, String> {
+        if attrs.is_empty() {
+            return Ok(statement);
+        }
+        let is_function = matches!(statement, Statement::Function { .. });
+        for attr in &attrs {
+            let arguments = &attr.arguments;
+            match attr.name.lexeme.as_str() {
+                "test" => {
+                    if !is_function {
+                        return Err("Attribute `test` can be used only on functions".to_string());
+                    }
+                    if !arguments.is_empty() {
+                        return Err("Attribute `test` takes no arguments".to_string());
+                    }
+                }
+                "inline" => {
+                    if !is_function {
+                        return Err("Attribute `inline` can be used only on functions".to_string());
+                    }
+                    let valid = match arguments.as_slice() {
+                        [] => true,
+                        [mode] => matches!(mode.token_type, Identifier { .. }) && INLINE_MODES.contains(&mode.lexeme.as_str()),
+                        _ => false,
+                    };
+                    if !valid {
+                        return Err("Attribute `inline` takes no arguments, `always` or `never`".to_string());
+                    }
+                }
+                "deprecated" => {
+                    let valid = match arguments.as_slice() {
+                        [] => true,
+                        [message] => matches!(message.token_type, StringT { .. }),
+                        _ => false,
+                    };
+                    if !valid {
+                        return Err("Attribute `deprecated` takes only a string message".to_string());
+                    }
+                }
+                "allow" => {
+                    if arguments.is_empty() {
+                        return Err("Attribute `allow` takes at least one lint".to_string());
+                    }
+                    for lint in arguments {
+                        if !matches!(lint.token_type, Identifier { .. }) || !LINTS.contains(&lint.lexeme.as_str()) {
+                            return Err(format!("Unknown lint `{}` in `allow`", lint.lexeme));
+                        }
+                    }
+                }
+                _ => return Err(format!("Unknown attribute `{}`", attr.name.lexeme)),
+            }
+        }
+        match &mut statement {
+            Statement::Let { attrs: target, .. } | Statement::Function { attrs: target, .. } | Statement::Class { attrs: target, .. } => {
+                *target = attrs;
+            }
+            _ => return Err("Attributes can be used only on functions, `let` and classes".to_string()),
+        }
+        Ok(statement)
+    }
+
+    /// Parses top level declaration with `pub` before it
+    fn visible_declaration(&mut self) -> Result<Statement, String> {
+        let visibility = self.visibility();
+        if visibility == Visibility::Private {
+            return Err("`private` can be used only on class members".to_string());
+        }
+        if self.variables.len() > 1 {
+            return Err("`pub` can be used only on top level declarations and class members".to_string());
+        }
+        if self.match_token(Let) {
+            return self.let_statement(visibility);
+        }
+        if self.match_tokens(vec![Const, Static]) {
+            return self.global_statement(visibility);
+        }
+        if self.match_token(Fun) {
+            return self.func_statement(visibility);
+        }
+        if self.match_token(Enum) {
+            return self.enum_statement(visibility);
+        }
+        if self.match_token(Interface) {
+            return self.interface_statement(visibility);
+        }
+        if self.match_token(Class) {
+            return self.class_statement(visibility);
+        }
+        Err("Declaration expected after `pub`".to_string())
+    }
+
+    /// Parses `const NAME: Type = value;` or `static mut NAME: Type = value;` after `const` or `static`
+    fn global_statement(&mut self, visibility: Visibility) -> Result<Statement, String> {
+        let keyword = self.previous();
+        if self.variables.len() > 1 {
+            return Err(format!("`{}` can be declared only on top level", keyword.lexeme));
+        }
+        let mutable = self.match_token(Mut);
+        if mutable && keyword.token_type == Const {
+            return Err("Constant can't be `mut`, use `static mut` instead".to_string());
+        }
+        let name = self.consume(Identifier { value: String::new() }, &format!("Identifier expected, for `{}` declaration.", keyword.lexeme))?;
+        if self.constants.contains_key(&name.lexeme) || self.statics.contains_key(&name.lexeme) {
+            return Err(format!("`{}` is already declared", name.lexeme));
+        }
+        let _ = self.consume(Colon, &format!("`:` expected, type of `{}` should be written", name.lexeme))?;
+        let _type = self.type_expr("Type expected")?;
+        let _ = self.consume(Equal, &format!("`=` expected, `{}` should be initialized", name.lexeme))?;
+
+        let value = self.operator_expression(ASSIGMENT)?;
+        // Statics can be initialized with values of immutable statics declared before them
+        let Some(known) = self.known_value(&value, keyword.token_type == Static) else {
+            return Err(format!("Initializer of `{}` should be known at compile time, found `{}`", name.lexeme, value.to_string()));
+        };
+        let _ = self.consume(Semicolon, &format!("`;` expected after `{}` declaration", keyword.lexeme))?;
+
+        self.declare_variable(&name);
+        if keyword.token_type == Const {
+            self.constants.insert(name.lexeme.clone(), known);
+            return Ok(Statement::Const { visibility, name, _type, value });
+        }
+        self.statics.insert(name.lexeme.clone(), (mutable, known));
+        Ok(Statement::Static { visibility, mutable, name, _type, value })
+    }
+
+    /// Returns `expression` with visible constants replaced by their values if it's known at compile time,
+    /// parsed expression is left as written and `Fold` pass replaces constants in it
+    fn known_value(&self, expression: &Expression, with_statics: bool) -> Option<Expression> {
+        let mut constants = self.constants.clone();
+        if with_statics {
+            for (name, (mutable, value)) in &self.statics {
+                if !mutable {
+                    constants.insert(name.clone(), value.clone());
+                }
+            }
+        }
+        // Local variables hide constants
+        constants.retain(|name, _| self.is_global(name));
+        let value = substitute(expression, &constants);
+        is_constant(&value).then_some(value)
+    }
+
+    /// Returns true if `name` isn't hidden by local variable
+    fn is_global(&self, name: &str) -> bool {
+        !self.variables[1..].iter().any(|scope| scope.contains(name))
+    }
+
+    /// Checks that `target` of assigment isn't a constant or immutable static
+    fn check_assignable(&self, target: &Expression) -> Result<(), String> {
+        match target {
+            Expression::Variable { name } if self.is_global(&name.lexeme) => {
+                if self.constants.contains_key(&name.lexeme) {
+                    return Err(format!("Cannot assign to constant `{}`", name.lexeme));
+                }
+                if let Some((false, _)) = self.statics.get(&name.lexeme) {
+                    return Err(format!("Cannot assign to immutable static `{}`, declare it as `static mut`", name.lexeme));
+                }
+                Ok(())
+            }
+            Expression::Get { object, .. } |
+            Expression::TupleIndex { object, .. } |
+            Expression::Index { object, .. } => self.check_assignable(object),
+            _ => Ok(()),
+        }
+    }
+
+    /// Parses `pub` or `private`, declarations without them are visible in their module
+    fn visibility(&mut self) -> Visibility {
+        if self.match_token(Pub) {
+            return Visibility::Public;
+        }
+        if self.match_token(Private) {
+            return Visibility::Private;
+        }
+        Visibility::Module
+    }
+
     fn assigment_statement(&mut self) -> Result<Statement, String> {
         let expression = self.expression()?;
-        Ok(Statement::Assigment { expression: expression })
+        self.end_of_expression_statement(Statement::Assigment { expression })
+    }
+
+    /// Expression statement ends with `;`, it can be omitted before `}`, at the end of file
+    /// and after expressions which end with block
+    fn end_of_expression_statement(&mut self, statement: Statement) -> Result<Statement, String> {
+        if self.match_token(Semicolon) || self.look_token(0, RightCurBrace) || self.is_at_end() {
+            return Ok(statement);
+        }
+        match &statement {
+            Statement::Assigment { expression: Expression::Block { .. } | Expression::If { .. } | Expression::Loop { .. } | Expression::Match { .. } } => Ok(statement),
+            Statement::Assigment { expression } => Err(format!("`;` expected after `{}`", expression.to_string())),
+            _ => Err(format!("`;` expected after `{}`", statement)),
+        }
     }
 
     fn block_statement(&mut self) -> Result<Statement, String> {
         let mut statements = Vec::new();
+        self.variables.push(HashSet::new());
         while !self.match_tokens(vec![RightCurBrace, EOF]) {
             statements.push(self.statement()?);
         }
+        self.variables.pop();
         if self.previous().token_type == EOF {
             return Err("Block statements wasn't closed".to_string());
         }
         Ok(Statement::Block { statements: statements })
     }
     
-    fn let_statement(&mut self) -> Result<Statement, String> {
+    fn let_statement(&mut self, visibility: Visibility) -> Result<Statement, String> {
 
         let mutable = self.match_token(Mut);
 
-        let name = self.consume(Identifier { value: String::new() }, "Identifier expected, for variable declaration.")?;
-        let _ = self.consume(Colon, "`:` expected")?;
-        let _type = self.consume(Identifier { value: String::new() }, "Type expected, for variable declaration.")?;
+        let pattern = self.pattern("Identifier expected, for variable declaration.")?;
+        let names: Vec<String> = pattern.bindings().iter().map(|name| name.lexeme.clone()).collect();
+        let names = names.join(", ");
+        let mut _type = None;
+        if self.match_token(Colon) {
+            _type = Some(self.type_expr("Type expected, for variable declaration.")?);
+        }
 
-        let mut defined = false;
-        let mut assigment_stmt = Statement::Assigment {
-            expression: Expression::Literal {
-                value: Token::new(
-                    Null,
-                    "null".to_string(),
-                    _type.possition
-                )
-            }
-        };
+        let mut value = None;
         if self.match_token(Equal) {
-            defined = true;
-            assigment_stmt = self.statement()?;
+            value = Some(self.expression()?);
+        } else if _type.is_none() {
+            return Err(format!("Type annotation or initializer expected for `{}`", names));
         }
         
         let _ = self.consume(Semicolon, "`;` expected after variable define statement")?;
+
+        for name in pattern.bindings() {
+            self.declare_variable(&name);
+        }
         
         Ok(
             Statement::Let {
+                attrs: vec![],
+                visibility,
                 mutable: mutable,
-                defined: defined,
                 _type: _type,
-                name: name,
-                value: Box::from(assigment_stmt)
+                pattern,
+                value
             }
         )
     }
     
-    fn func_statement(&mut self) -> Result<Statement, String> {
+    fn func_statement(&mut self, visibility: Visibility) -> Result<Statement, String> {
         let name = self.consume(Identifier { value: String::new() }, "Identifier expected, for function declaration.")?;
+        let generics = self.type_parameters()?;
+        self.function_declaration(visibility, name, generics)
+    }
 
+    /// Parses arguments, returned type and body of function, type parameters are in scope
+    fn function_declaration(&mut self, visibility: Visibility, name: Token, generics: Vec<TypeParameter>) -> Result<Statement, String> {
         let _ = self.consume(LeftParen, "`(` expected")?;
+        let arguments = self.arguments()?;
+
+        let _type = self.return_type()?;
+
+        let _ = self.consume(LeftCurBrace, "`{` expected")?;
+
+        self.variables.push(argument_names(&arguments));
+        let loops = std::mem::take(&mut self.loops);
+        let block = self.block_statement()?;
+        self.loops = loops;
+        self.variables.pop();
+
+        Ok(
+            Statement::Function { attrs: vec![], visibility, name, generics, _type, arguments, block: Box::from(block) }
+        )
+    }
+
+    /// Parses `<T: Ord + Eq, U>` after name of function or class, returns nothing if there is no `<`
+    fn type_parameters(&mut self) -> Result<Vec<TypeParameter>, String> {
+        let mut generics: Vec<TypeParameter> = vec![];
+        if !self.match_token(Less) {
+            return Ok(generics);
+        }
+        loop {
+            let name = self.consume(Identifier { value: String::new() }, "Type parameter name expected")?;
+            if generics.iter().any(|other| other.name.lexeme == name.lexeme) {
+                return Err(format!("Type parameter `{}` is declared more than once", name.lexeme));
+            }
+            let mut bounds = vec![];
+            if self.match_token(Colon) {
+                loop {
+                    let bound = self.consume(Identifier { value: String::new() }, "Interface name expected after `:`")?;
+                    if !self.interfaces.contains_key(&bound.lexeme) && !BUILTIN_BOUNDS.contains(&bound.lexeme.as_str()) {
+                        return Err(format!("Unknown interface `{}`", bound.lexeme));
+                    }
+                    bounds.push(bound);
+                    if !self.match_token(Plus) {
+                        break;
+                    }
+                }
+            }
+            generics.push(TypeParameter { name, bounds });
+            if !self.match_token(Comma) {
+                break;
+            }
+        }
+        self.consume_greater("`>` expected after type parameters")?;
+        Ok(generics)
+    }
+
+    /// Parses `-> Type`, functions without it return `void`
+    fn return_type(&mut self) -> Result<TypeExpr, String> {
+        if self.match_token(MinusGreater) {
+            return self.type_expr("Returned type expected after `->`");
+        }
+        Ok(TypeExpr::Named { name: Token::new(Null, "void".to_string(), self.previous().possition) })
+    }
+
+    /// Parses `interface Name { fun required(); fun with_default() { ... } }` after `interface`
+    fn interface_statement(&mut self, visibility: Visibility) -> Result<Statement, String> {
+        let name = self.consume(Identifier { value: String::new() }, "Identifier expected, for interface declaration.")?;
+        if !self.hoisted.remove(&name.lexeme) && self.interfaces.contains_key(&name.lexeme) {
+            return Err(format!("Interface `{}` is already declared", name.lexeme));
+        }
+        let _ = self.consume(LeftCurBrace, "`{` expected")?;
+        let mut methods: Vec<InterfaceMethod> = vec![];
+        while !self.match_token(RightCurBrace) {
+            let _ = self.consume(Fun, "Only methods can be declared in interface")?;
+            let method = self.consume(Identifier { value: String::new() }, "Identifier expected, for method declaration.")?;
+            if methods.iter().any(|other| other.name.lexeme == method.lexeme) {
+                return Err(format!("Method `{}` is declared more than once in interface `{}`", method.lexeme, name.lexeme));
+            }
+            let _ = self.consume(LeftParen, "`(` expected")?;
+            let arguments = self.arguments()?;
+            let _type = self.return_type()?;
+            let mut block = None;
+            if !self.match_token(Semicolon) {
+                let _ = self.consume(LeftCurBrace, "`;` or `{` expected after method signature")?;
+                block = Some(Box::from(self.block_statement()?));
+            }
+            methods.push(InterfaceMethod { name: method, _type, arguments, block });
+        }
+        self.interfaces.insert(name.lexeme.clone(), methods.clone());
+        Ok(Statement::Interface { visibility, name, methods })
+    }
+
+    /// Parses `class Name : Interface { let field: Type; fun method() { ... } }` after `class`
+    fn class_statement(&mut self, visibility: Visibility) -> Result<Statement, String> {
+        let name = self.consume(Identifier { value: String::new() }, "Identifier expected, for class declaration.")?;
+        if !self.classes.insert(name.lexeme.clone()) {
+            return Err(format!("Class `{}` is already declared", name.lexeme));
+        }
+        let generics = self.type_parameters()?;
+
+        self.variables.push(HashSet::new());
+        let result = self.class_declaration(visibility, name, generics);
+        self.variables.pop();
+        result
+    }
+
+    /// Parses interfaces and members of class, type parameters are in scope
+    fn class_declaration(&mut self, visibility: Visibility, name: Token, generics: Vec<TypeParameter>) -> Result<Statement, String> {
+        let mut interfaces: Vec<Token> = vec![];
+        if self.match_token(Colon) {
+            loop {
+                let interface = self.consume(Identifier { value: String::new() }, "Interface name expected after `:`")?;
+                if !self.interfaces.contains_key(&interface.lexeme) {
+                    return Err(format!("Unknown interface `{}`", interface.lexeme));
+                }
+                interfaces.push(interface);
+                if !self.match_token(Comma) {
+                    break;
+                }
+            }
+        }
+        let _ = self.consume(LeftCurBrace, "`{` expected")?;
+        let mut fields = vec![];
+        let mut methods = vec![];
+        while !self.match_token(RightCurBrace) {
+            let attrs = self.attributes()?;
+            let member_visibility = self.visibility();
+            if self.match_token(Let) {
+                let field = self.let_statement(member_visibility)?;
+                fields.push(self.attach_attributes(attrs, field)?);
+            } else if self.match_token(Fun) {
+                let method = self.func_statement(member_visibility)?;
+                methods.push(self.attach_attributes(attrs, method)?);
+            } else {
+                return Err(format!("Only fields and methods can be declared in class `{}`", name.lexeme));
+            }
+        }
+        let mut members: Vec<Token> = vec![];
+        for member in fields.iter().chain(&methods) {
+            let names = match member {
+                Statement::Let { pattern, .. } => pattern.bindings(),
+                Statement::Function { name, .. } => vec![name.clone()],
+                _ => vec![],
+            };
+            for member in names {
+                if members.iter().any(|other| other.lexeme == member.lexeme) {
+                    return Err(format!("`{}` is declared more than once in class `{}`", member.lexeme, name.lexeme));
+                }
+                members.push(member);
+            }
+        }
+
+        for interface in &interfaces {
+            for required in &self.interfaces[&interface.lexeme] {
+                let implemented = methods.iter().find_map(|method| match method {
+                    Statement::Function { name, _type, arguments, visibility, .. } if name.lexeme == required.name.lexeme => {
+                        Some((arguments, _type, visibility))
+                    }
+                    _ => None,
+                });
+                match implemented {
+                    Some((_, _, Visibility::Private)) => {
+                        return Err(format!(
+                            "Method `{}` of class `{}` implements interface `{}` and can't be private",
+                            required.name.lexeme,
+                            name.lexeme,
+                            interface.lexeme
+                        ));
+                    }
+                    Some((arguments, _type, _)) => {
+                        let expected = signature(&required.arguments, &required._type);
+                        let found = signature(arguments, _type);
+                        if expected != found {
+                            return Err(format!(
+                                "Method `{}` of class `{}` should be `{}` as declared in interface `{}`, found `{}`",
+                                required.name.lexeme,
+                                name.lexeme,
+                                expected,
+                                interface.lexeme,
+                                found
+                            ));
+                        }
+                    }
+                    None if required.block.is_none() => {
+                        return Err(format!(
+                            "Class `{}` doesn't implement method `{}` of interface `{}`",
+                            name.lexeme,
+                            required.name.lexeme,
+                            interface.lexeme
+                        ));
+                    }
+                    None => {}
+                }
+            }
+        }
+        Ok(Statement::Class { attrs: vec![], visibility, name, generics, interfaces, fields, methods })
+    }
+
+    /// Parses arguments of function or lambda after `(`
+    fn arguments(&mut self) -> Result<Vec<Argument>, String> {
         let mut arguments: Vec<Argument> = vec![];
         let mut starts_optional_args = false;
         while !self.match_token(RightParen) {
@@ -152,7 +797,7 @@ impl Parser {
 
             let _ = self.consume(Colon, "`:` expected")?;
 
-            let arg_type = self.consume(Identifier { value: String::new() }, "Argument type expected")?;
+            let arg_type = self.type_expr("Argument type expected")?;
 
             if self.match_token(Equal) {
                 starts_optional_args = true;
@@ -165,50 +810,686 @@ impl Parser {
             }
             self.match_token(Comma);
         }
+        Ok(arguments)
+    }
 
-        let mut _type = Token::new(Null, "void".to_string(), self.previous().possition);
-        
+    /// Parses `fun (x: i32) -> i32 { ... }` after `fun`
+    fn lambda(&mut self) -> Result<Expression, String> {
+        let _ = self.consume(LeftParen, "`(` expected after `fun`")?;
+        let arguments = self.arguments()?;
+
+        let _type = self.return_type()?;
+
+        let _ = self.consume(LeftCurBrace, "`{` expected")?;
+        self.variables.push(argument_names(&arguments));
+        let loops = std::mem::take(&mut self.loops);
+        let body = self.block_statement()?;
+        self.loops = loops;
+        self.variables.pop();
+        let captures = find_captures(&arguments, &body);
+        Ok(Expression::Lambda { arguments, _type: Some(_type), body: Box::from(body), captures })
+    }
+
+    /// Parses `(x: i32) => x * 2` or `(x: i32) -> i32 => { ... }` after `(`
+    fn arrow_lambda(&mut self) -> Result<Expression, String> {
+        let arguments = self.arguments()?;
+
+        let mut _type = None;
         if self.match_token(MinusGreater) {
-            _type = self.consume(Identifier { value: String::new() }, "Returned type expected after `->`")?;
+            _type = Some(self.type_expr("Returned type expected after `->`")?);
+        }
+
+        let _ = self.consume(EqualGreater, "`=>` expected")?;
+        self.variables.push(argument_names(&arguments));
+        let loops = std::mem::take(&mut self.loops);
+        let body = if self.match_token(LeftCurBrace) {
+            self.block_statement()?
+        } else {
+            let expression = self.expression()?;
+            Statement::Return { returned: Some(expression) }
+        };
+        self.loops = loops;
+        self.variables.pop();
+        let captures = find_captures(&arguments, &body);
+        Ok(Expression::Lambda { arguments, _type, body: Box::from(body), captures })
+    }
+
+    /// Parses `match value { pattern if guard => body, ... }` after `match`
+    fn match_expression(&mut self) -> Result<Expression, String> {
+        let value = self.expression()?;
+        let _ = self.consume(LeftCurBrace, "`{` expected after matched value")?;
+        let mut arms = vec![];
+        while !self.match_token(RightCurBrace) {
+            let pattern = self.match_pattern("Pattern expected")?;
+            self.check_pattern(&pattern)?;
+            self.variables.push(pattern.bindings().into_iter().map(|name| name.lexeme).collect());
+            let mut guard = None;
+            if self.match_token(If) {
+                guard = Some(self.expression()?);
+            }
+            let _ = self.consume(EqualGreater, "`=>` expected after pattern")?;
+            if self.match_token(LeftCurBrace) {
+                let body = self.block_statement()?;
+                self.variables.pop();
+                arms.push(MatchArm { pattern, guard, body: Box::from(body) });
+                self.match_token(Comma);
+            } else {
+                let expression = self.expression()?;
+                self.variables.pop();
+                arms.push(MatchArm { pattern, guard, body: Box::from(Statement::Assigment { expression }) });
+                if !self.match_token(Comma) {
+                    let _ = self.consume(RightCurBrace, "`,` or `}` expected after match arm")?;
+                    break;
+                }
+            }
+        }
+        if arms.is_empty() {
+            return Err("Match should have at least one arm".to_string());
+        }
+        Ok(Expression::Match { value: Box::from(value), arms })
+    }
+
+    /// Parses block after `{` which gives value of its last expression if it has no `;`
+    fn block_expression(&mut self) -> Result<Expression, String> {
+        let mut statements = vec![];
+        let mut value = None;
+        self.variables.push(HashSet::new());
+        while !self.match_token(RightCurBrace) {
+            if self.is_at_end() {
+                return Err("Block wasn't closed".to_string());
+            }
+            if self.starts_statement() {
+                statements.push(self.statement()?);
+                continue;
+            }
+            let expression = self.expression()?;
+            if self.match_token(Semicolon) {
+                statements.push(Statement::Assigment { expression });
+            } else if self.match_token(RightCurBrace) {
+                value = Some(Box::from(expression));
+                break;
+            } else if matches!(expression, Expression::Block { .. } | Expression::If { .. } | Expression::Loop { .. } | Expression::Match { .. }) {
+                statements.push(Statement::Assigment { expression });
+            } else {
+                return Err(format!("`;` or `}}` expected after `{}`", expression.to_string()));
+            }
+        }
+        self.variables.pop();
+        Ok(Expression::Block { statements, value })
+    }
+
+    /// Returns true if current token starts a declaration or a statement which isn't an expression
+    fn starts_statement(&mut self) -> bool {
+        self.look_tokens(0, vec![
+            Let, Const, Static, Enum, Interface, Class, For, Range, Break, Continue, Return, While, DoWhile,
+            Pub, Private, Module, Import
+        ]) ||
+        (self.look_token(0, Fun) && self.look_token(1, Identifier { value: String::new() })) ||
+        (self.look_token(0, Hash) && self.look_token(1, LeftBrace)) ||
+        (self.look_token(0, Label { value: String::new() }) && !self.look_token(2, Loop))
+    }
+
+    /// Parses `if (condition) { ... } else { ... }` used as a value after `if`
+    fn if_expression(&mut self) -> Result<Expression, String> {
+        let _ = self.consume(LeftParen, "`(` expected")?;
+        let condition = self.expression()?;
+        let _ = self.consume(RightParen, "`)` expected")?;
+        let _ = self.consume(LeftCurBrace, "`{` expected after condition of `if`")?;
+        let then_branch = self.block_expression()?;
+        let mut else_branch = None;
+        if self.match_token(Else) {
+            let branch = if self.match_token(If) {
+                self.if_expression()?
+            } else {
+                let _ = self.consume(LeftCurBrace, "`{` or `if` expected after `else`")?;
+                self.block_expression()?
+            };
+            else_branch = Some(Box::from(branch));
         }
+        Ok(Expression::If { condition: Box::from(condition), then_branch: Box::from(then_branch), else_branch })
+    }
 
+    /// Parses `enum Name { Unit, Tuple(i32, bool), Struct { a: i32 } }` after `enum`
+    fn enum_statement(&mut self, visibility: Visibility) -> Result<Statement, String> {
+        let name = self.consume(Identifier { value: String::new() }, "Identifier expected, for enum declaration.")?;
+        if !self.hoisted.remove(&name.lexeme) && self.enums.contains_key(&name.lexeme) {
+            return Err(format!("Enum `{}` is already declared", name.lexeme));
+        }
         let _ = self.consume(LeftCurBrace, "`{` expected")?;
+        let mut variants: Vec<Variant> = vec![];
+        while !self.match_token(RightCurBrace) {
+            let variant = self.consume(Identifier { value: String::new() }, "Variant name expected")?;
+            if variants.iter().any(|other| other.name.lexeme == variant.lexeme) {
+                return Err(format!("Variant `{}` is declared more than once in enum `{}`", variant.lexeme, name.lexeme));
+            }
+            let fields = if self.match_token(LeftParen) {
+                let mut values = vec![];
+                while !self.match_token(RightParen) {
+                    values.push(self.type_expr("Type of variant field expected")?);
+                    if !self.match_token(Comma) {
+                        let _ = self.consume(RightParen, "`)` or `,` expected in variant")?;
+                        break;
+                    }
+                }
+                VariantFields::Tuple { values }
+            } else if self.match_token(LeftCurBrace) {
+                let mut fields: Vec<(Token, TypeExpr)> = vec![];
+                while !self.match_token(RightCurBrace) {
+                    let field = self.consume(Identifier { value: String::new() }, "Field name expected")?;
+                    if fields.iter().any(|(other, _)| other.lexeme == field.lexeme) {
+                        return Err(format!("Field `{}` is declared more than once in variant `{}`", field.lexeme, variant.lexeme));
+                    }
+                    let _ = self.consume(Colon, "`:` expected")?;
+                    fields.push((field, self.type_expr("Type of variant field expected")?));
+                    if !self.match_token(Comma) {
+                        let _ = self.consume(RightCurBrace, "`}` or `,` expected in variant")?;
+                        break;
+                    }
+                }
+                VariantFields::Struct { fields }
+            } else {
+                VariantFields::Unit
+            };
+            variants.push(Variant { name: variant, fields });
+            if !self.match_token(Comma) {
+                let _ = self.consume(RightCurBrace, "`}` or `,` expected after variant")?;
+                break;
+            }
+        }
+        self.enums.insert(name.lexeme.clone(), variants.clone());
+        Ok(Statement::Enum { visibility, name, variants })
+    }
 
-        let block = self.block_statement()?;
+    /// Parses `::Variant(..)`, `::Variant { .. }` or `::Variant` after enum name
+    fn enum_variant(&mut self, enum_name: Token) -> Result<Expression, String> {
+        let _ = self.consume(ColonColon, "`::` expected")?;
+        let variant = self.consume(Identifier { value: String::new() }, "Variant name expected after `::`")?;
+        let fields = if self.match_token(LeftParen) {
+            let mut values = vec![];
+            while !self.match_token(RightParen) {
+                values.push(self.expression()?);
+                if !self.match_token(Comma) {
+                    let _ = self.consume(RightParen, "`)` or `,` expected")?;
+                    break;
+                }
+            }
+            VariantFields::Tuple { values }
+        } else if self.look_token(0, LeftCurBrace) && self.look_token(1, Identifier { value: String::new() }) && self.look_token(2, Colon) {
+            self.advance();
+            let mut fields = vec![];
+            while !self.match_token(RightCurBrace) {
+                let field = self.consume(Identifier { value: String::new() }, "Field name expected")?;
+                let _ = self.consume(Colon, "`:` expected")?;
+                fields.push((field, self.expression()?));
+                if !self.match_token(Comma) {
+                    let _ = self.consume(RightCurBrace, "`}` or `,` expected")?;
+                    break;
+                }
+            }
+            VariantFields::Struct { fields }
+        } else {
+            VariantFields::Unit
+        };
 
-        Ok(
-            Statement::Function { name, _type, arguments, block: Box::from(block) }
-        )
+        let declared = self.find_variant(&enum_name, &variant)?;
+        match (&declared.fields, &fields) {
+            (VariantFields::Unit, VariantFields::Unit) => {}
+            (VariantFields::Tuple { values: types }, VariantFields::Tuple { values }) => {
+                if types.len() != values.len() {
+                    return Err(format!(
+                        "Variant `{}::{}` has {} fields, found {}",
+                        enum_name.lexeme,
+                        variant.lexeme,
+                        types.len(),
+                        values.len()
+                    ));
+                }
+            }
+            (VariantFields::Struct { fields: types }, VariantFields::Struct { fields: values }) => {
+                for (position, (field, _)) in values.iter().enumerate() {
+                    if values[..position].iter().any(|(other, _)| other.lexeme == field.lexeme) {
+                        return Err(format!("Field `{}` is set more than once", field.lexeme));
+                    }
+                    if !types.iter().any(|(name, _)| name.lexeme == field.lexeme) {
+                        return Err(format!("No field `{}` in variant `{}::{}`", field.lexeme, enum_name.lexeme, variant.lexeme));
+                    }
+                }
+                for (name, _) in types {
+                    if !values.iter().any(|(field, _)| field.lexeme == name.lexeme) {
+                        return Err(format!("Missing field `{}` in variant `{}::{}`", name.lexeme, enum_name.lexeme, variant.lexeme));
+                    }
+                }
+            }
+            (declared_fields, _) => return Err(variant_form_error(&enum_name, &variant, declared_fields)),
+        }
+        Ok(Expression::EnumVariant { enum_name, variant, fields })
+    }
+
+    fn find_variant(&self, enum_name: &Token, variant: &Token) -> Result<Variant, String> {
+        let variants = self.enums.get(&enum_name.lexeme).ok_or(format!("Unknown enum `{}`", enum_name.lexeme))?;
+        variants.iter()
+            .find(|declared| declared.name.lexeme == variant.lexeme)
+            .cloned()
+            .ok_or(format!("No variant `{}` in enum `{}`", variant.lexeme, enum_name.lexeme))
+    }
+
+    /// Checks that variant patterns match declared enums
+    fn check_pattern(&self, pattern: &Pattern) -> Result<(), String> {
+        match pattern {
+            Pattern::Tuple { elements } => {
+                for element in elements {
+                    self.check_pattern(element)?;
+                }
+                Ok(())
+            }
+            Pattern::Variant { enum_name, variant, fields } => {
+                let declared = self.find_variant(enum_name, variant)?;
+                let mut checked = vec![];
+                match (&declared.fields, fields) {
+                    (VariantFields::Unit, VariantFields::Unit) => {}
+                    (VariantFields::Tuple { values: types }, VariantFields::Tuple { values }) => {
+                        if types.len() != values.len() {
+                            return Err(format!(
+                                "Variant `{}::{}` has {} fields, found {}",
+                                enum_name.lexeme,
+                                variant.lexeme,
+                                types.len(),
+                                values.len()
+                            ));
+                        }
+                        checked.extend(values);
+                    }
+                    (VariantFields::Struct { fields: types }, VariantFields::Struct { fields: values }) => {
+                        // Fields which aren't written match everything
+                        for (field, value) in values {
+                            if !types.iter().any(|(name, _)| name.lexeme == field.lexeme) {
+                                return Err(format!("No field `{}` in variant `{}::{}`", field.lexeme, enum_name.lexeme, variant.lexeme));
+                            }
+                            checked.push(value);
+                        }
+                    }
+                    (declared_fields, _) => return Err(variant_form_error(enum_name, variant, declared_fields)),
+                }
+                for value in checked {
+                    self.check_pattern(value)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn declare_variable(&mut self, name: &Token) {
+        if let Some(scope) = self.variables.last_mut() {
+            scope.insert(name.lexeme.clone());
+        }
+    }
+
+    /// Returns true if `(` at current token is closed by `)` followed by `=>` or `->`
+    fn is_arrow_lambda(&self) -> bool {
+        let mut depth = 0;
+        for (position, token) in self.tokens.iter().enumerate().skip(self.current) {
+            match token.token_type {
+                LeftParen => depth += 1,
+                RightParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return self.tokens.get(position + 1)
+                            .is_some_and(|next| matches!(next.token_type, EqualGreater | MinusGreater));
+                    }
+                }
+                EOF => return false,
+                _ => {}
+            }
+        }
+        false
     }
     
+    /// Parses `a` or `(a, (b, c))`, `msg` is returned if there is no pattern
+    fn pattern(&mut self, msg: &str) -> Result<Pattern, String> {
+        let pattern = self.match_pattern(msg)?;
+        if pattern.is_refutable() {
+            return Err(format!("Refutable pattern `{}` can be used only in `match`", pattern));
+        }
+        Ok(pattern)
+    }
+
+    /// Parses pattern of `match` arm, unlike `let` it can contain literals and ranges
+    fn match_pattern(&mut self, msg: &str) -> Result<Pattern, String> {
+        let pattern = self.pattern_element(msg)?;
+        let bindings = pattern.bindings();
+        for (position, name) in bindings.iter().enumerate() {
+            if bindings[..position].iter().any(|other| other.lexeme == name.lexeme) {
+                return Err(format!("Identifier `{}` is bound more than once in the same pattern", name.lexeme));
+            }
+        }
+        Ok(pattern)
+    }
+
+    fn pattern_element(&mut self, msg: &str) -> Result<Pattern, String> {
+        if self.match_token(LeftParen) {
+            let mut elements = vec![];
+            while !self.match_token(RightParen) {
+                elements.push(self.pattern_element(msg)?);
+                if !self.match_token(Comma) {
+                    let _ = self.consume(RightParen, "`)` or `,` expected in tuple pattern")?;
+                    break;
+                }
+            }
+            return Ok(Pattern::Tuple { elements });
+        }
+        if self.look_tokens(0, vec![DotDot, DotDotEqual]) {
+            return self.range_pattern(None);
+        }
+        if let Some(value) = self.pattern_literal()? {
+            if self.look_tokens(0, vec![DotDot, DotDotEqual]) {
+                return self.range_pattern(Some(value));
+            }
+            return Ok(Pattern::Literal { value });
+        }
+        let name = self.consume(Identifier { value: String::new() }, msg)?;
+        if self.match_token(ColonColon) {
+            return self.variant_pattern(name, msg);
+        }
+        if name.lexeme == "_" {
+            return Ok(Pattern::Wildcard);
+        }
+        Ok(Pattern::Binding { name })
+    }
+
+    /// Parses `Variant(a, b)`, `Variant { a, b: c }` or `Variant` after `Enum::`
+    fn variant_pattern(&mut self, enum_name: Token, msg: &str) -> Result<Pattern, String> {
+        let variant = self.consume(Identifier { value: String::new() }, "Variant name expected after `::`")?;
+        let fields = if self.match_token(LeftParen) {
+            let mut values = vec![];
+            while !self.match_token(RightParen) {
+                values.push(self.pattern_element(msg)?);
+                if !self.match_token(Comma) {
+                    let _ = self.consume(RightParen, "`)` or `,` expected in variant pattern")?;
+                    break;
+                }
+            }
+            VariantFields::Tuple { values }
+        } else if self.match_token(LeftCurBrace) {
+            let mut fields: Vec<(Token, Pattern)> = vec![];
+            while !self.match_token(RightCurBrace) {
+                let field = self.consume(Identifier { value: String::new() }, "Field name expected")?;
+                if fields.iter().any(|(other, _)| other.lexeme == field.lexeme) {
+                    return Err(format!("Field `{}` is matched more than once", field.lexeme));
+                }
+                // `{ w }` is the same as `{ w: w }`
+                let value = if self.match_token(Colon) {
+                    self.pattern_element(msg)?
+                } else {
+                    Pattern::Binding { name: field.clone() }
+                };
+                fields.push((field, value));
+                if !self.match_token(Comma) {
+                    let _ = self.consume(RightCurBrace, "`}` or `,` expected in variant pattern")?;
+                    break;
+                }
+            }
+            VariantFields::Struct { fields }
+        } else {
+            VariantFields::Unit
+        };
+        Ok(Pattern::Variant { enum_name, variant, fields })
+    }
+
+    /// Parses `..end` or `..=end` after start of range pattern
+    fn range_pattern(&mut self, start: Option<Token>) -> Result<Pattern, String> {
+        let inclusive = self.advance().token_type == DotDotEqual;
+        let end = self.pattern_literal()?;
+        if end.is_none() && (inclusive || start.is_none()) {
+            return Err("End of range pattern expected".to_string());
+        }
+        let pattern = Pattern::Range { start, end, inclusive };
+        let _ = pattern.get_type()?;
+        Ok(pattern)
+    }
+
+    /// Parses literal in pattern, `-1` is one literal
+    fn pattern_literal(&mut self) -> Result<Option<Token>, String> {
+        if self.look_token(0, Minus) && self.look_tokens(1, vec![
+            Int { value: String::new(), num_type: NumberType::UntypedInt },
+            Float { value: String::new(), num_type: NumberType::UntypedFloat },
+        ]) {
+            let minus = self.advance();
+            let number = self.advance();
+            let token_type = match number.token_type {
+                Int { value, num_type } => Int { value: format!("-{}", value), num_type },
+                Float { value, num_type } => Float { value: format!("-{}", value), num_type },
+                _ => return Err("Number expected after `-`".to_string()),
+            };
+            let possition = (minus.possition.0, minus.possition.1, number.possition.2);
+            return Ok(Some(Token::new(token_type, format!("-{}", number.lexeme), possition)));
+        }
+        if self.look_tokens(0, vec![
+            Int { value: String::new(), num_type: NumberType::UntypedInt },
+            Float { value: String::new(), num_type: NumberType::UntypedFloat },
+            StringT { value: String::new() },
+            Char { value: ' ' },
+            BoolT { value: false },
+            Null,
+        ]) {
+            return Ok(Some(self.advance()));
+        }
+        Ok(None)
+    }
+
+    /// Parses type annotation, `msg` is returned if there is no type
+    fn type_expr(&mut self, msg: &str) -> Result<TypeExpr, String> {
+        let _type = self.type_element(msg)?;
+        if self.match_token(Question) {
+            return Ok(TypeExpr::Nullable { inner: Box::from(_type) });
+        }
+        Ok(_type)
+    }
+
+    /// Parses type without `?` after it
+    fn type_element(&mut self, msg: &str) -> Result<TypeExpr, String> {
+        if self.match_token(LeftBrace) {
+            let element = self.type_expr("Type of array elements expected")?;
+            let mut size = None;
+            if self.match_token(Semicolon) {
+                let length = self.operator_expression(ASSIGMENT)?;
+                if self.known_value(&length, false).is_none() {
+                    return Err(format!("Array length should be known at compile time, found `{}`", length.to_string()));
+                }
+                size = Some(Box::from(length));
+            }
+            let _ = self.consume(RightBrace, "`]` expected after array type")?;
+            return Ok(TypeExpr::Array { element: Box::from(element), size });
+        }
+        if self.match_token(LeftParen) {
+            let mut elements = vec![];
+            let mut is_tuple = true;
+            while !self.match_token(RightParen) {
+                elements.push(self.type_expr("Type of tuple element expected")?);
+                if !self.match_token(Comma) {
+                    let _ = self.consume(RightParen, "`)` or `,` expected in tuple type")?;
+                    is_tuple = elements.len() != 1;
+                    break;
+                }
+            }
+            if !is_tuple {
+                // `(i32)` is just `i32`
+                return Ok(elements.remove(0));
+            }
+            return Ok(TypeExpr::Tuple { elements });
+        }
+        if self.match_token(Fun) {
+            let _ = self.consume(LeftParen, "`(` expected after `fun`")?;
+            let mut arguments = vec![];
+            while !self.match_token(RightParen) {
+                arguments.push(self.type_expr("Type of function argument expected")?);
+                if !self.match_token(Comma) {
+                    let _ = self.consume(RightParen, "`)` or `,` expected in function type")?;
+                    break;
+                }
+            }
+            let returned = self.return_type()?;
+            return Ok(TypeExpr::Function { arguments, returned: Box::from(returned) });
+        }
+        if self.match_token(Hash) {
+            let _ = self.consume(LeftCurBrace, "`{` expected after `#`")?;
+            let key = self.type_expr("Type of map keys expected")?;
+            let _ = self.consume(Colon, "`:` expected")?;
+            let value = self.type_expr("Type of map values expected")?;
+            let _ = self.consume(RightCurBrace, "`}` expected after map type")?;
+            return Ok(TypeExpr::Map { key: Box::from(key), value: Box::from(value) });
+        }
+        let name = self.consume(Identifier { value: String::new() }, msg)?;
+        if self.match_token(Less) {
+            let arguments = self.type_arguments()?;
+            return Ok(TypeExpr::Generic { name, arguments });
+        }
+        Ok(TypeExpr::Named { name })
+    }
+
+    /// Parses `i32, String>` of `List<i32>` or `max::<i32>`, `<` should be already consumed
+    fn type_arguments(&mut self) -> Result<Vec<TypeExpr>, String> {
+        let mut arguments = vec![self.type_expr("Generic argument expected")?];
+        while self.match_token(Comma) {
+            arguments.push(self.type_expr("Generic argument expected")?);
+        }
+        self.consume_greater("`>` expected after generic arguments")?;
+        Ok(arguments)
+    }
+
+    /// Consumes `>` which closes generic arguments, `>>` in `List<List<i32>>` is split in two
+    fn consume_greater(&mut self, msg: &str) -> Result<(), String> {
+        let token = self.peek();
+        let rest = match token.token_type {
+            Greater => {
+                self.advance();
+                return Ok(());
+            }
+            GreaterGreater => Greater,
+            GreaterEqual => Equal,
+            GreaterGreaterEqual => GreaterEqual,
+            _ => return Err(msg.to_string()),
+        };
+        let (line, start, end) = token.possition;
+        self.tokens[self.current] = Token::new(rest, token.lexeme[1..].to_string(), (line, start + 1, end));
+        Ok(())
+    }
+
     fn range_statement(&mut self) -> Result<Statement, String> {
+        let range = self.range_expression()?;
+        self.end_of_expression_statement(Statement::Assigment { expression: range })
+    }
+
+    /// `range[start; end]` is the same as `start..end`
+    fn range_expression(&mut self) -> Result<Expression, String> {
         let _ = self.consume(LeftBrace, "`[` expected")?;
-        let one = self.statement()?;
+        let start = self.expression()?;
         let _ = self.consume(Semicolon, "`;` expected")?;
-        let two = self.statement()?;
+        let end = self.expression()?;
         let _ = self.consume(RightBrace, "`]` expected")?;
-        Ok(Statement::RangeIter { start_num: Box::from(one), end_num: Box::from(two) })
+        Ok(Expression::Range { start: Some(Box::from(start)), end: Some(Box::from(end)), inclusive: false })
     }
 
+    /// Parses `break;`, `break 'outer;` or `break value;` after `break`
     fn break_statement(&mut self) -> Result<Statement, String> {
-        let _ = self.consume(Semicolon, "`;` expected")?;
-        Ok(Statement::Break)
+        let label = self.label();
+        let mut value = None;
+        if !self.match_token(Semicolon) {
+            value = Some(self.expression()?);
+            let _ = self.consume(Semicolon, "`;` expected")?;
+        }
+        let target = self.target_loop(&label, "break")?;
+        if value.is_some() {
+            if !target.gives_value {
+                return Err("`break` with a value can be used only in `loop`".to_string());
+            }
+            if target.empty_break {
+                return Err("All `break`s of loop should give a value or none of them".to_string());
+            }
+            target.value_break = true;
+        } else {
+            if target.value_break {
+                return Err("All `break`s of loop should give a value or none of them".to_string());
+            }
+            target.empty_break = true;
+        }
+        Ok(Statement::Break { label, value })
     }
     
     fn continue_statement(&mut self) -> Result<Statement, String> {
+        let label = self.label();
         let _ = self.consume(Semicolon, "`;` expected")?;
-        Ok(Statement::Continue)
+        let _ = self.target_loop(&label, "continue")?;
+        Ok(Statement::Continue { label })
+    }
+
+    /// Parses `'outer` after `break` or `continue`, `None` if there is no label
+    fn label(&mut self) -> Option<Token> {
+        if self.match_token(Label { value: String::new() }) {
+            return Some(self.previous());
+        }
+        None
+    }
+
+    /// Returns loop which is left by `break` or `continue` with `label`
+    fn target_loop(&mut self, label: &Option<Token>, keyword: &str) -> Result<&mut LoopContext, String> {
+        match label {
+            Some(label) => {
+                self.loops.iter_mut().rev()
+                    .find(|context| context.label.as_ref() == Some(&label.lexeme))
+                    .ok_or(format!("Undeclared label `{}`", label.lexeme))
+            }
+            None => self.loops.last_mut().ok_or(format!("`{}` can be used only inside of loop", keyword)),
+        }
+    }
+
+    /// Starts body of loop, labels of nested loops should differ
+    fn enter_loop(&mut self, label: &Option<Token>, gives_value: bool) -> Result<(), String> {
+        if let Some(label) = label {
+            if self.loops.iter().any(|context| context.label.as_ref() == Some(&label.lexeme)) {
+                return Err(format!("Label `{}` is already used by enclosing loop", label.lexeme));
+            }
+        }
+        self.loops.push(LoopContext {
+            label: label.as_ref().map(|label| label.lexeme.clone()),
+            gives_value,
+            value_break: false,
+            empty_break: false,
+        });
+        Ok(())
+    }
+
+    /// Parses `'outer: for`, `'outer: while` or `'outer: loop` statement
+    fn labeled_statement(&mut self) -> Result<Statement, String> {
+        let label = self.advance();
+        self.advance();
+        if self.match_token(For) {
+            return self.for_statement(Some(label));
+        }
+        if self.match_token(While) {
+            return self.while_statement(Some(label));
+        }
+        if self.match_token(DoWhile) {
+            return self.do_while_statement(Some(label));
+        }
+        if self.match_token(Loop) {
+            return self.loop_statement(Some(label));
+        }
+        Err(format!("Loop expected after label `{}`", label.lexeme))
     }
     
     fn return_statement(&mut self) -> Result<Statement, String> {
-        let returned = self.statement()?;
+        if self.match_token(Semicolon) {
+            return Ok(Statement::Return { returned: None });
+        }
+        let returned = self.expression()?;
         let _ = self.consume(Semicolon, "`;` expected")?;
-        Ok(Statement::Return { returned: Box::from(returned) })
+        Ok(Statement::Return { returned: Some(returned) })
     }
     
     fn if_else_statement(&mut self) -> Result<Statement, String> {
         let _ = self.consume(LeftParen, "`(` expected")?;
-        let condition = self.statement()?;
+        let condition = self.expression()?;
         let _ = self.consume(RightParen, "`)` expected")?;
         let if_block = self.statement()?;
         let mut else_block = None;
@@ -216,58 +1497,87 @@ impl Parser {
             else_block = Some(Box::from(self.statement()?));
         }
 
-        Ok(Statement::IfElse { condition: Box::from(condition), if_block: Box::from(if_block), else_block: else_block })
+        Ok(Statement::IfElse { condition, if_block: Box::from(if_block), else_block })
     }
 
-    fn for_statement(&mut self) -> Result<Statement, String> {
+    fn for_statement(&mut self, label: Option<Token>) -> Result<Statement, String> {
         let _ = self.consume(LeftParen, "`(` expected")?;
 
-        let var = self.consume(Identifier { value: String::new() }, "Variable name expected")?;
+        let pattern = self.pattern("Variable name expected")?;
         
         let _ = self.consume(In, "Variable name expected")?;
 
-        let container = self.statement()?;
+        let container = match self.match_token(Range) {
+            true => self.range_expression()?,
+            false => self.expression()?,
+        };
         
         let _ = self.consume(RightParen, "`)` expected")?;
         let a = self.consume(LeftCurBrace, "`{` expected")?;
+        self.variables.push(pattern.bindings().into_iter().map(|name| name.lexeme).collect());
+        self.enter_loop(&label, false)?;
         let block = self.block_statement()?;
+        self.loops.pop();
+        self.variables.pop();
         Ok(
             Statement::For {
-                var,
-                container: Box::from(container),
+                label,
+                pattern,
+                container: Box::from(Statement::Assigment { expression: container }),
                 block: Box::from(block)
             }
         )
     }
 
-    fn while_statement(&mut self) -> Result<Statement, String> {
+    fn while_statement(&mut self, label: Option<Token>) -> Result<Statement, String> {
         let _ = self.consume(LeftParen, "`(` expected")?;
-        let condition = self.statement()?;
+        let condition = self.expression()?;
         let _ = self.consume(RightParen, "`)` expected")?;
         let _ = self.consume(LeftCurBrace, "`{` expected")?;
+        self.enter_loop(&label, false)?;
         let block = self.block_statement()?;
-        Ok(Statement::While { condition: Box::from(condition), block: Box::from(block) })
+        self.loops.pop();
+        Ok(Statement::While { label, condition, block: Box::from(block) })
     }
 
-    fn do_while_statement(&mut self) -> Result<Statement, String> {
+    fn do_while_statement(&mut self, label: Option<Token>) -> Result<Statement, String> {
         let _ = self.consume(LeftCurBrace, "`{` expected")?;
+        self.enter_loop(&label, false)?;
         let block = self.block_statement()?;
+        self.loops.pop();
         let _ = self.consume(While, "`while` expected after block statement")?;
         let _ = self.consume(LeftParen, "`(` expected")?;
-        let condition = self.statement()?;
+        let condition = self.expression()?;
         let _ = self.consume(RightParen, "`)` expected")?;
         let _ = self.consume(Semicolon, "`;` expected after variable define statement")?;
-        Ok(Statement::DoWhile { block: Box::from(block), condition: Box::from(condition) })
+        Ok(Statement::DoWhile { label, block: Box::from(block), condition })
     }
 
-    fn loop_statement(&mut self) -> Result<Statement, String> {
+    fn loop_statement(&mut self, label: Option<Token>) -> Result<Statement, String> {
         let _ = self.consume(LeftCurBrace, "`{` expected")?;
-        Ok(Statement::Loop { block: Box::from(self.block_statement()?) })
+        self.enter_loop(&label, true)?;
+        let block = self.block_statement()?;
+        self.loops.pop();
+        Ok(Statement::Loop { label, block: Box::from(block) })
+    }
+
+    /// Parses body of `loop` used as a value after `{`
+    fn loop_expression(&mut self, label: Option<Token>) -> Result<Expression, String> {
+        self.enter_loop(&label, true)?;
+        let body = self.block_expression()?;
+        self.loops.pop();
+        Ok(Expression::Loop { label, body: Box::from(body) })
     }
 
     fn func_use_statement(&mut self) -> Result<Statement, String> {
         let name = self.consume(Identifier { value: String::new() }, "Function name expected")?;
         let _ = self.consume(LeftParen, "`(` expected")?;
+        let arguments = self.call_arguments()?;
+        self.end_of_expression_statement(Statement::FunctionUse { name, arguments })
+    }
+
+    /// Parses arguments of function call, `(` should be already consumed
+    fn call_arguments(&mut self) -> Result<Vec<UseArgument>, String> {
         let mut arguments: Vec<UseArgument> = vec![];
         let mut starts_optional_args = false;
         while !self.match_token(RightParen) {
@@ -284,200 +1594,265 @@ impl Parser {
             }
             self.match_token(Comma);
         }
-        Ok(Statement::FunctionUse { name, arguments })
+        Ok(arguments)
     }
 
 
     fn expression(&mut self) -> Result<Expression, String> {
-        self.assigment()?.optimize_expression()
+        self.operator_expression(ASSIGMENT)
     }
-    
-    fn assigment(&mut self) -> Result<Expression, String> {
 
-        self.ternary()
-    }
-
-    fn ternary(&mut self) -> Result<Expression, String> {
-        let mut result: Expression = self.logical_or()?;
-        if self.match_token(Question) {
-            let true_expression = self.expression()?;
-
-            let _ = self.consume(Colon, "`:` expected after left result");
-            let false_expression = self.expression()?;
-            
-            result = Expression::Ternary {
-                result: Box::from(result),
-                true_expression: Box::from(true_expression),
-                false_expression: Box::from(false_expression)
-            };
+    /// Parses expression which has no operators binding looser than `min_precedence`
+    fn operator_expression(&mut self, min_precedence: u8) -> Result<Expression, String> {
+        let mut result = match operators::prefix(&self.peek().token_type) {
+            Some(operator) if operator.precedence >= min_precedence => {
+                let token = self.advance();
+                self.prefix_operator(operator, token)?
+            }
+            _ => self.primary()?,
+        };
+        let mut previous: Option<(&Operator, Token)> = None;
+        while let Some(operator) = operators::infix(&self.peek().token_type) {
+            if operator.precedence < min_precedence || !self.can_follow(operator, &result) {
+                break;
+            }
+            let token = self.advance();
+            if let Some((previous, previous_token)) = &previous {
+                if previous.associativity == Associativity::None && previous.precedence == operator.precedence {
+                    return Err(format!("`{}` can't be chained with `{}`, add parentheses", previous_token.lexeme, token.lexeme));
+                }
+            }
+            result = self.infix_operator(operator, token.clone(), result)?;
+            previous = Some((operator, token));
         }
         Ok(result)
     }
 
-    fn logical_or(&mut self) -> Result<Expression, String> {
-        let mut result = self.logical_and()?;
-        while self.match_token(Or) {
-            let op = self.previous();
-            let right = self.logical_and()?;
-            result = Expression::EqualtyComparison {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
+    /// Returns true if `operator` can be applied to already parsed `left`
+    fn can_follow(&self, operator: &Operator, left: &Expression) -> bool {
+        if operator.fixity == Fixity::Postfix && matches!(left, Expression::Increment { postfix: true, .. }) {
+            return false;
         }
-        Ok(result)
-    }
-
-    fn logical_and(&mut self) -> Result<Expression, String> {
-        let mut result = self.bitwise_or()?;
-        while self.match_token(And) {
-            let op = self.previous();
-            let right = self.bitwise_or()?;
-            result = Expression::EqualtyComparison {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
+        match operator.node {
+            Node::Call => left.is_callable(),
+            Node::Index => left.is_indexable(),
+            _ => true,
         }
-        Ok(result)
     }
 
-    fn bitwise_or(&mut self) -> Result<Expression, String> {
-        let mut result = self.bitwise_xor()?;
-        while self.match_token(Bar) {
-            let op = self.previous();
-            let right = self.bitwise_xor()?;
-            result = Expression::Binary {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
+    /// Builds expression of prefix `operator`, `token` should be already consumed
+    fn prefix_operator(&mut self, operator: &Operator, token: Token) -> Result<Expression, String> {
+        match operator.node {
+            Node::Range => {
+                let end = self.range_end(operator, &token)?;
+                Ok(Expression::Range { start: None, end, inclusive: token.token_type == DotDotEqual })
+            }
+            Node::Increment => {
+                let target = self.operator_expression(operator.operand_precedence())?;
+                self.increment(token, target, false)
+            }
+            _ => {
+                let right = self.operator_expression(operator.operand_precedence())?;
+                Ok(Expression::Unary { operator: token, right: Box::from(right) })
+            }
         }
-        Ok(result)
     }
 
-    fn bitwise_xor(&mut self) -> Result<Expression, String> {
-        let mut result = self.bitwise_and()?;
-        while self.match_token(Caret) {
-            let op = self.previous();
-            let right = self.bitwise_and()?;
-            result = Expression::Binary {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
+    /// Builds expression of infix or postfix `operator`, `token` should be already consumed
+    fn infix_operator(&mut self, operator: &Operator, token: Token, left: Expression) -> Result<Expression, String> {
+        let left = Box::from(left);
+        match operator.node {
+            Node::Field => self.field(*left, token.token_type == QuestionDot),
+            Node::Call => {
+                let arguments = self.call_arguments()?;
+                Ok(Expression::Call { callee: left, type_arguments: vec![], arguments })
+            }
+            Node::Index => self.index(*left),
+            Node::Increment => self.increment(token, *left, true),
+            Node::Ternary => {
+                let true_expression = self.expression()?;
+                let _ = self.consume(Colon, "`:` expected after left result")?;
+                let false_expression = self.expression()?;
+                Ok(Expression::Ternary {
+                    result: left,
+                    true_expression: Box::from(true_expression),
+                    false_expression: Box::from(false_expression)
+                })
+            }
+            Node::Range => {
+                let end = self.range_end(operator, &token)?;
+                Ok(Expression::Range { start: Some(left), end, inclusive: token.token_type == DotDotEqual })
+            }
+            Node::Assign => {
+                let value = self.operator_expression(operator.operand_precedence())?;
+                if !left.is_place() {
+                    return Err(format!("Invalid assigment target `{}` at possition {:?}", left.to_string(), token.possition));
+                }
+                self.check_assignable(&left)?;
+                Ok(Expression::Assign { target: left, operator: token, value: Box::from(value) })
+            }
+            node => {
+                let right = Box::from(self.operator_expression(operator.operand_precedence())?);
+                Ok(match node {
+                    Node::NullCoalescing => Expression::NullCoalescing { left, operator: token, right },
+                    Node::Logical => Expression::Logical { left, operator: token, right },
+                    Node::Comparison => Expression::EqualtyComparison { left, operator: token, right },
+                    _ => Expression::Binary { left, operator: token, right },
+                })
+            }
         }
-        Ok(result)
     }
 
-    fn bitwise_and(&mut self) -> Result<Expression, String> {
-        let mut result = self.equalty()?;
-        while self.match_token(Ampersant) {
-            let op = self.previous();
-            let right = self.equalty()?;
-            result = Expression::Binary {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
+    /// Parses end of range, `a..` is open range when nothing can follow it
+    fn range_end(&mut self, operator: &Operator, token: &Token) -> Result<Option<Box<Expression>>, String> {
+        let inclusive = token.token_type == DotDotEqual;
+        if inclusive || !(self.is_at_end() || self.look_tokens(0, vec![RightParen, RightBrace, RightCurBrace, Comma, Semicolon])) {
+            return Ok(Some(Box::from(self.operator_expression(operator.operand_precedence())?)));
         }
-        Ok(result)
+        Ok(None)
     }
 
-    fn equalty(&mut self) -> Result<Expression, String> {
-        let mut result = self.comparison()?;
-        while self.match_tokens(vec![EqualEqual, BangEqual]) {
-            let op = self.previous();
-            let right = self.comparison()?;
-            result = Expression::EqualtyComparison {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
+    fn increment(&mut self, operator: Token, target: Expression, postfix: bool) -> Result<Expression, String> {
+        if !target.is_place() {
+            return Err(format!("Invalid `{}` target `{}` at possition {:?}", operator.lexeme, target.to_string(), operator.possition));
         }
-        Ok(result)
+        self.check_assignable(&target)?;
+        Ok(Expression::Increment { operator, target: Box::from(target), postfix })
     }
-
-    fn comparison(&mut self) -> Result<Expression, String> {
-        let mut result = self.shift()?;
-        while self.match_tokens(vec![LessEqual, Less, GreaterEqual, Greater]) {
-            let op = self.previous();
-            let right = self.shift()?;
-            result = Expression::EqualtyComparison {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
+    
+    /// Parses `a.field` and tuple fields `a.0`, `.` or `?.` should be already consumed
+    fn field(&mut self, object: Expression, optional: bool) -> Result<Expression, String> {
+        if !optional && self.look_token(0, Int { value: String::new(), num_type: NumberType::UntypedInt }) {
+            let index = self.advance();
+            return Ok(Expression::TupleIndex { object: Box::from(object), index });
         }
-        Ok(result)
-    }
-
-    fn shift(&mut self) -> Result<Expression, String> {
-        let mut result = self.term()?;
-        while self.match_tokens(vec![LessLess, GreaterGreater]) {
-            let op = self.previous();
-            let right = self.term()?;
-            result = Expression::Binary {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
+        if !optional && self.look_token(0, Float { value: String::new(), num_type: NumberType::UntypedFloat }) {
+            // `t.0.1` is lexed as `t`, `.`, `0.1`
+            let token = self.advance();
+            let mut result = object;
+            for lexeme in token.lexeme.split('.') {
+                let index = Token::new(
+                    Int { value: lexeme.to_string(), num_type: NumberType::UntypedInt },
+                    lexeme.to_string(),
+                    token.possition
+                );
+                result = Expression::TupleIndex { object: Box::from(result), index };
+            }
+            return Ok(result);
         }
-        Ok(result)
+        let name = self.consume(Identifier { value: String::new() }, "Field name expected after `.`")?;
+        Ok(Expression::Get {
+            object: Box::from(object),
+            name,
+            optional
+        })
     }
 
-    fn term(&mut self) -> Result<Expression, String> {
-        let mut result = self.factor()?;
-        while self.match_tokens(vec![Plus, Minus]) {
-            let op = self.previous();
-            let right = self.factor()?;
-            result = Expression::Binary {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
-        }
-        Ok(result)
+    /// Parses `a[i]` and slices `a[start..end]`, `[` should be already consumed
+    fn index(&mut self, object: Expression) -> Result<Expression, String> {
+        let index = self.expression()?;
+        let _ = self.consume(RightBrace, "`]` expected after index")?;
+        Ok(Expression::Index { object: Box::from(object), index: Box::from(index) })
     }
 
-    fn factor(&mut self) -> Result<Expression, String> {
-        let mut result = self.unary()?;
-        while self.match_tokens(vec![Star, Slash, Persent]) {
-            let op = self.previous();
-            let right = self.unary()?;
-            result = Expression::Binary {
-                left: Box::from(result),
-                operator: op,
-                right: Box::from(right)
-            };
+    /// Parses `[1, 2, 3]` and `[0; 16]`, `[` should be already consumed
+    fn array(&mut self) -> Result<Expression, String> {
+        let mut elements = vec![];
+        while !self.match_token(RightBrace) {
+            elements.push(self.expression()?);
+            if elements.len() == 1 && self.match_token(Semicolon) {
+                let count = self.operator_expression(ASSIGMENT)?;
+                let _ = self.consume(RightBrace, "`]` expected after array length")?;
+                return Ok(Expression::ArrayRepeat { value: Box::from(elements.remove(0)), count: Box::from(count) });
+            }
+            if !self.match_token(Comma) {
+                let _ = self.consume(RightBrace, "`]` or `,` expected after array element")?;
+                break;
+            }
         }
-        Ok(result)
+        Ok(Expression::Array { elements })
     }
 
-    fn unary(&mut self) -> Result<Expression, String> {
-        if self.match_tokens(vec![Bang, Minus]) {
-            let op = self.previous();
-            let right = self.unary()?;
-            Ok(Expression::Unary {
-                operator: op,
-                right: Box::from(right),
-            })
-        } else {
-            self.primary()
+    /// Parses `#{ "a": 1, "b": 2 }`, `#` should be already consumed
+    fn map(&mut self) -> Result<Expression, String> {
+        let _ = self.consume(LeftCurBrace, "`{` expected after `#`")?;
+        let mut entries = vec![];
+        while !self.match_token(RightCurBrace) {
+            let key = self.expression()?;
+            let _ = self.consume(Colon, "`:` expected after map key")?;
+            let value = self.expression()?;
+            entries.push((key, value));
+            if !self.match_token(Comma) {
+                let _ = self.consume(RightCurBrace, "`}` or `,` expected after map entry")?;
+                break;
+            }
         }
+        Ok(Expression::Map { entries })
     }
-    
+
     fn primary(&mut self) -> Result<Expression, String> {
         let token = self.peek();
         let result;
         match token.token_type {
+            LeftParen if self.is_arrow_lambda() => {
+                self.advance();
+                result = self.arrow_lambda()?;
+            }
+            Fun => {
+                self.advance();
+                result = self.lambda()?;
+            }
+            Match => {
+                self.advance();
+                result = self.match_expression()?;
+            }
+            If => {
+                self.advance();
+                result = self.if_expression()?;
+            }
+            Loop => {
+                self.advance();
+                let _ = self.consume(LeftCurBrace, "`{` expected after `loop`")?;
+                result = self.loop_expression(None)?;
+            }
+            Label { .. } if self.look_token(1, Colon) && self.look_token(2, Loop) => {
+                let label = Some(self.advance());
+                self.advance();
+                self.advance();
+                let _ = self.consume(LeftCurBrace, "`{` expected after `loop`")?;
+                result = self.loop_expression(label)?;
+            }
+            LeftCurBrace => {
+                self.advance();
+                result = self.block_expression()?;
+            }
             LeftParen => {
                 self.advance();
-                let expr = self.expression()?;
-                self.consume(RightParen, "Expected ')'")?;
-                result = Expression::Grouping {
-                    expression: Box::from(expr),
-                };
+                let mut elements = vec![];
+                let mut is_tuple = true;
+                while !self.match_token(RightParen) {
+                    elements.push(self.expression()?);
+                    if !self.match_token(Comma) {
+                        self.consume(RightParen, "Expected ')'")?;
+                        is_tuple = elements.len() != 1;
+                        break;
+                    }
+                }
+                if is_tuple {
+                    result = Expression::Tuple { elements };
+                } else {
+                    result = Expression::Grouping {
+                        expression: Box::from(elements.remove(0)),
+                    };
+                }
+            }
+            LeftBrace => {
+                self.advance();
+                result = self.array()?;
+            }
+            Hash => {
+                self.advance();
+                result = self.map()?;
             }
             Int {..} | Float {..} | StringT {..} | BoolT {..} | Char {..} | Null => {
                 self.advance();
@@ -485,7 +1860,25 @@ impl Parser {
                     value: token,
                 }
             }
-            Identifier { .. } => {
+            Identifier { .. } if self.look_token(1, ColonColon) && self.look_token(2, Less) => {
+                // `max::<i32>(a, b)`
+                self.advance();
+                self.advance();
+                self.advance();
+                let type_arguments = self.type_arguments()?;
+                let _ = self.consume(LeftParen, "`(` expected after type arguments")?;
+                let arguments = self.call_arguments()?;
+                result = Expression::Call {
+                    callee: Box::from(Expression::Variable { name: token }),
+                    type_arguments,
+                    arguments
+                };
+            }
+            Identifier { .. } if self.look_token(1, ColonColon) => {
+                self.advance();
+                result = self.enum_variant(token)?;
+            }
+            Identifier { .. } | This => {
                 self.advance();
                 result = Expression::Variable {
                     name: token,
@@ -512,12 +1905,20 @@ impl Parser {
         if self.is_at_end() {
             false
         } else {
-            if self.tokens[self.current + pos].token_type.eq_token(token_type) {
-                true
-            } else {
-                false
+            match self.tokens.get(self.current + pos) {
+                Some(token) => token.token_type.eq_token(token_type),
+                None => false,
+            }
+        }
+    }
+
+    fn look_tokens(&mut self, pos: usize, token_types: Vec<TokenType>) -> bool {
+        for token_type in token_types {
+            if self.look_token(pos, token_type) {
+                return true;
             }
         }
+        false
     }
 
     fn match_token(&mut self, token_type: TokenType) -> bool {
@@ -562,25 +1963,111 @@ impl Parser {
         self.peek().token_type == EOF
     }
 
-    fn synchronize(&mut self) {
-        self.advance();
-
+    /// Skips the rest of statement which started at `start`, blocks opened by it are skipped too.
+    /// Statement ends at `;` outside of brackets, declarations and statements ending with block
+    /// can also end at `}` which closes their block
+    fn synchronize(&mut self, start: usize) {
+        let ends_with_block = self.ends_with_block(start);
+        let mut depth = 0;
+        for token in &self.tokens[start..self.current] {
+            match token.token_type {
+                LeftCurBrace | LeftParen | LeftBrace => depth += 1,
+                RightCurBrace | RightParen | RightBrace if depth > 0 => depth -= 1,
+                _ => {}
+            }
+        }
+        if self.current > start && depth == 0 {
+            match self.previous().token_type {
+                Semicolon => return,
+                RightCurBrace if ends_with_block && !self.look_token(0, Else) => return,
+                _ => {}
+            }
+        }
         while !self.is_at_end() {
-            if self.previous().token_type == Semicolon {
-                return;
+            // Next statement can start only after at least one token of failed one is skipped
+            if self.current > start && depth == 0 {
+                match self.peek().token_type {
+                    Class | Fun | Return | For |
+                    If | While | DoWhile | Loop |
+                    Break | Continue | Let | Const |
+                    Static | Enum | Interface | Import |
+                    Module => return,
+                    _ => (),
+                }
             }
+            match self.advance().token_type {
+                LeftCurBrace | LeftParen | LeftBrace => depth += 1,
+                RightCurBrace if depth > 0 => {
+                    depth -= 1;
+                    // `else` continues `if` after its block
+                    if depth == 0 && ends_with_block && !self.look_token(0, Else) {
+                        return;
+                    }
+                }
+                RightParen | RightBrace if depth > 0 => depth -= 1,
+                Semicolon if depth == 0 => return,
+                _ => {}
+            }
+        }
+    }
 
-            match self.peek().token_type {
-                And | Or | If | Else |
-                Class | Super | This | Fun |
-                Return | For | While | DoWhile |
-                Loop | Break | Continue | Null |
-                Let => return,
-                _ => (),
+    /// Returns true if statement which starts at `start` can end with `}`, not only with `;`
+    fn ends_with_block(&self, start: usize) -> bool {
+        let mut position = start;
+        // Attributes and visibility are written before declaration
+        while let Some(token) = self.tokens.get(position) {
+            match token.token_type {
+                Hash => {
+                    while self.tokens.get(position).is_some_and(|token| token.token_type != RightBrace) {
+                        position += 1;
+                    }
+                    position += 1;
+                }
+                Pub | Private => position += 1,
+                _ => break,
             }
-            
-            self.advance();
         }
+        self.tokens.get(position).is_some_and(|token| matches!(
+            token.token_type,
+            Fun | Enum | Interface | Class | For | If | While | DoWhile | Loop | Match | LeftCurBrace | Label { .. }
+        ))
     }
 }
 
+fn variant_form_error<T>(enum_name: &Token, variant: &Token, declared: &VariantFields<T>) -> String {
+    let form = match declared {
+        VariantFields::Unit => "",
+        VariantFields::Tuple { .. } => "(..)",
+        VariantFields::Struct { .. } => " { .. }",
+    };
+    format!("Variant `{0}::{1}` should be written as `{0}::{1}{2}`", enum_name.lexeme, variant.lexeme, form)
+}
+
+fn copy_declarations<T: Clone>(from: &HashMap<String, T>, to: &mut HashMap<String, T>, names: &[String]) {
+    for (name, declaration) in from {
+        if names.contains(name) {
+            to.insert(name.clone(), declaration.clone());
+        }
+    }
+}
+
+/// Returns names of arguments for scope of function or lambda body
+fn argument_names(arguments: &[Argument]) -> HashSet<String> {
+    arguments.iter()
+        .map(|argument| match argument {
+            Argument::NotOptional { name, .. } |
+            Argument::Optional { name, .. } => name.lexeme.clone(),
+        })
+        .collect()
+}
+
+/// Returns `fun(i32) -> bool` for method with given arguments and returned type
+fn signature(arguments: &[Argument], _type: &TypeExpr) -> String {
+    let arguments = arguments.iter()
+        .map(|argument| match argument {
+            Argument::NotOptional { _type, .. } |
+            Argument::Optional { _type, .. } => _type.clone(),
+        })
+        .collect();
+    Ty::from_type(&TypeExpr::Function { arguments, returned: Box::from(_type.clone()) }, &vec![]).name()
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+
+use crate::lexer::token::{Token, TokenType};
+
+use super::{expr::{Expression, Type}, stmt::VariantFields, types::TypeExpr};
+
+/// Left side of `let`, variable of `for` and arm of `match`
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+    Binding { name: Token },
+    /// `_` matches everything without binding
+    Wildcard,
+    /// `1`, `-1`, `'a'`, `"text"`, `true` or `null`, only in `match`
+    Literal { value: Token },
+    /// `1..=5`, `'a'..'z'`, `10..` or `..0`, only in `match`
+    Range { start: Option<Token>, end: Option<Token>, inclusive: bool },
+    /// `(q, r)`, `(a,)` is a tuple with one element
+    Tuple { elements: Vec<Pattern> },
+    /// `Shape::Circle(r)`, `Shape::Rect { w: w, h }` or `Shape::Empty`, only in `match`
+    Variant { enum_name: Token, variant: Token, fields: VariantFields<Pattern> },
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pattern::Binding { name } => write!(f, "{}", name.to_string()),
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Literal { value } => write!(f, "{}", value.lexeme),
+            Pattern::Range { start, end, inclusive } => {
+                write!(
+                    f,
+                    "{}{}{}",
+                    start.as_ref().map(|start| start.lexeme.clone()).unwrap_or_default(),
+                    if *inclusive { "..=" } else { ".." },
+                    end.as_ref().map(|end| end.lexeme.clone()).unwrap_or_default()
+                )
+            }
+            Pattern::Tuple { elements } => {
+                let elements: Vec<String> = elements.iter().map(|element| element.to_string()).collect();
+                write!(f, "({})", elements.join(", "))
+            }
+            Pattern::Variant { enum_name, variant, fields } => {
+                write!(f, "{}::{}{}", enum_name.lexeme, variant.lexeme, fields.to_string(|field| field.to_string()))
+            }
+        }
+    }
+}
+
+impl Pattern {
+    /// Returns true if pattern doesn't match some values, such patterns can be used only in `match`
+    pub fn is_refutable(&self) -> bool {
+        match self {
+            Pattern::Binding { .. } | Pattern::Wildcard => false,
+            Pattern::Literal { .. } | Pattern::Range { .. } | Pattern::Variant { .. } => true,
+            Pattern::Tuple { elements } => elements.iter().any(|element| element.is_refutable()),
+        }
+    }
+
+    pub fn get_type(&self) -> Result<Type, String> {
+        match self {
+            Pattern::Binding { .. } | Pattern::Wildcard => Ok(Type::Unknown),
+            Pattern::Literal { value } => Expression::Literal { value: value.clone() }.check_and_get_type(),
+            Pattern::Range { start, end, .. } => {
+                let mut bounds_type = Type::Unknown;
+                for bound in [start, end].into_iter().flatten() {
+                    let bound_result = match bound.token_type {
+                        TokenType::Int { .. } => Type::Integer,
+                        TokenType::Char { .. } => Type::Char,
+                        _ => return Err(format!("Range pattern bounds should be integers or chars, found `{}`", bound.lexeme)),
+                    };
+                    if !bounds_type.agrees_with(bound_result) {
+                        return Err(format!("Range pattern bounds should have the same type, found `{}`", self));
+                    }
+                    bounds_type = bound_result;
+                }
+                Ok(bounds_type)
+            }
+            Pattern::Tuple { .. } => Ok(Type::Tuple),
+            Pattern::Variant { .. } => Ok(Type::Enum),
+        }
+    }
+
+    /// Checks that two patterns can match values of the same type
+    pub fn agrees_with(&self, other: &Pattern) -> Result<(), String> {
+        match (self, other) {
+            (Pattern::Tuple { elements }, Pattern::Tuple { elements: others }) => {
+                if elements.len() != others.len() {
+                    return Err(format!(
+                        "Expected a tuple with {} elements, found one with {} elements",
+                        elements.len(),
+                        others.len()
+                    ));
+                }
+                for (element, other) in elements.iter().zip(others) {
+                    element.agrees_with(other)?;
+                }
+                Ok(())
+            }
+            (Pattern::Variant { enum_name, .. }, Pattern::Variant { enum_name: other_name, .. }) => {
+                if enum_name.lexeme != other_name.lexeme {
+                    return Err(format!("Mismatched types of patterns `{}` and `{}`", self, other));
+                }
+                Ok(())
+            }
+            _ => {
+                if !self.get_type()?.agrees_with(other.get_type()?) {
+                    return Err(format!("Mismatched types of patterns `{}` and `{}`", self, other));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn bindings(&self) -> Vec<Token> {
+        match self {
+            Pattern::Binding { name } => vec![name.clone()],
+            Pattern::Wildcard | Pattern::Literal { .. } | Pattern::Range { .. } => vec![],
+            Pattern::Tuple { elements } => elements.iter().flat_map(|element| element.bindings()).collect(),
+            Pattern::Variant { fields, .. } => fields.values().iter().flat_map(|field| field.bindings()).collect(),
+        }
+    }
+
+    /// Checks that tuple pattern has the same number of elements as tuple type
+    pub fn check_type(&self, _type: &TypeExpr) -> Result<(), String> {
+        match (self, _type) {
+            (Pattern::Tuple { elements }, TypeExpr::Tuple { elements: types }) => {
+                if elements.len() != types.len() {
+                    return Err(format!(
+                        "Expected a tuple with {} elements, found one with {} elements",
+                        types.len(),
+                        elements.len()
+                    ));
+                }
+                for (element, _type) in elements.iter().zip(types) {
+                    element.check_type(_type)?;
+                }
+                Ok(())
+            }
+            (Pattern::Tuple { .. }, _) => Err(format!("Can't destructure `{}` type as tuple", _type)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that tuple pattern has the same number of elements as tuple literal
+    pub fn check_value(&self, value: &Expression) -> Result<(), String> {
+        match (self, value) {
+            (Pattern::Tuple { elements }, Expression::Tuple { elements: values }) => {
+                if elements.len() != values.len() {
+                    return Err(format!(
+                        "Expected a tuple with {} elements, found one with {} elements",
+                        elements.len(),
+                        values.len()
+                    ));
+                }
+                for (element, value) in elements.iter().zip(values) {
+                    element.check_value(value)?;
+                }
+                Ok(())
+            }
+            (Pattern::Tuple { .. }, Expression::Literal { value }) => {
+                Err(format!("Can't destructure `{}` as tuple", value.lexeme))
+            }
+            _ => Ok(()),
+        }
+    }
+}
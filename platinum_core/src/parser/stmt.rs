@@ -1,40 +1,176 @@
+use std::fmt;
+
 use crate::lexer::token::Token;
 
-use super::expr::Expression;
+use super::{expr::Expression, pattern::Pattern, types::{TypeExpr, TypeParameter}};
+
+/// Where declaration can be used
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Visibility {
+    /// `pub`, can be imported by other modules
+    Public,
+    /// Default, visible only in its module
+    Module,
+    /// `private` class member, visible only inside of its class
+    Private,
+}
+
+/// Writes modifier as it is written before declaration, nothing for default
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Visibility::Public => f.write_str("pub "),
+            Visibility::Module => Ok(()),
+            Visibility::Private => f.write_str("private "),
+        }
+    }
+}
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Argument {
-    NotOptional { name: Token, _type: Token },
-    Optional { name: Token, _type: Token, value: Expression},
+    NotOptional { name: Token, _type: TypeExpr },
+    Optional { name: Token, _type: TypeExpr, value: Expression},
+}
+
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Argument::NotOptional { name, _type } => write!(f, "{}: {}", name.lexeme, _type),
+            Argument::Optional { name, _type, value } => {
+                write!(f, "{}: {} = {}", name.lexeme, _type, value.to_string())
+            }
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum UseArgument {
     Expr { value: Expression},
     Optional { name: Token, value: Expression},
 }
 
-#[derive(Debug, PartialEq)]
+/// Values of enum variant: declared types, construction arguments or subpatterns
+#[derive(Debug, PartialEq, Clone)]
+pub enum VariantFields<T> {
+    /// `Empty`
+    Unit,
+    /// `Circle(f64)`
+    Tuple { values: Vec<T> },
+    /// `Rect { w: f64, h: f64 }`
+    Struct { fields: Vec<(Token, T)> },
+}
+
+impl<T> VariantFields<T> {
+    pub fn to_string(&self, value_to_string: impl Fn(&T) -> String) -> String {
+        match self {
+            VariantFields::Unit => String::new(),
+            VariantFields::Tuple { values } => {
+                let values: Vec<String> = values.iter().map(&value_to_string).collect();
+                format!("({})", values.join(", "))
+            }
+            VariantFields::Struct { fields } => {
+                let fields: Vec<String> = fields.iter()
+                    .map(|(name, value)| format!("{}: {}", name.lexeme, value_to_string(value)))
+                    .collect();
+                format!(" {{ {} }}", fields.join(", "))
+            }
+        }
+    }
+
+    /// Returns values in the written order
+    pub fn values(&self) -> Vec<&T> {
+        match self {
+            VariantFields::Unit => vec![],
+            VariantFields::Tuple { values } => values.iter().collect(),
+            VariantFields::Struct { fields } => fields.iter().map(|(_, value)| value).collect(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Variant {
+    pub name: Token,
+    pub fields: VariantFields<TypeExpr>,
+}
+
+/// Method of `interface`, `block` is its default implementation
+#[derive(Debug, PartialEq, Clone)]
+pub struct InterfaceMethod {
+    pub name: Token,
+    pub _type: TypeExpr,
+    pub arguments: Vec<Argument>,
+    pub block: Option<Box<Statement>>,
+}
+
+/// `#[deprecated("use x")]` written before function, `let` or class
+#[derive(Debug, PartialEq, Clone)]
+pub struct Attribute {
+    pub name: Token,
+    /// Identifiers and literals inside of parentheses
+    pub arguments: Vec<Token>,
+}
+
+/// Lints which can be turned off by `#[allow(..)]`
+pub const LINTS: [&str; 3] = ["unused", "dead_code", "deprecated"];
+/// Arguments of `#[inline(..)]`
+pub const INLINE_MODES: [&str; 2] = ["always", "never"];
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.arguments.is_empty() {
+            return write!(f, "#[{}]", self.name.lexeme);
+        }
+        let arguments: Vec<String> = self.arguments.iter().map(|argument| argument.lexeme.clone()).collect();
+        write!(f, "#[{}({})]", self.name.lexeme, arguments.join(", "))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum Statement {
     Block { statements: Vec<Statement> },
     Assigment { expression: Expression },
-    Let { mutable: bool, defined: bool, _type: Token, name: Token, value: Box<Statement>},
-    Function { name: Token, _type: Token, arguments: Vec<Argument>, block: Box<Statement>},
+    /// `value` is `None` for `let x: i32;`
+    Let { attrs: Vec<Attribute>, visibility: Visibility, mutable: bool, _type: Option<TypeExpr>, pattern: Pattern, value: Option<Expression> },
+    /// `const MAX: u32 = 10;`, `value` is folded at compile time
+    Const { visibility: Visibility, name: Token, _type: TypeExpr, value: Expression },
+    /// `static mut COUNTER: u32 = 0;`, statics are initialized in order of declaration
+    Static { visibility: Visibility, mutable: bool, name: Token, _type: TypeExpr, value: Expression },
+    Function { attrs: Vec<Attribute>, visibility: Visibility, name: Token, generics: Vec<TypeParameter>, _type: TypeExpr, arguments: Vec<Argument>, block: Box<Statement>},
     FunctionUse { name: Token, arguments: Vec<UseArgument>},
-    IfElse { condition: Box<Statement>, if_block: Box<Statement>, else_block: Option<Box<Statement>> },
-    Loop { block: Box<Statement> },
-    For { var: Token, container: Box<Statement>, block: Box<Statement> },
-    RangeIter { start_num: Box<Statement>, end_num: Box<Statement> },
-    While { condition: Box<Statement>, block: Box<Statement> },
-    DoWhile { block: Box<Statement>, condition: Box<Statement> },
-    Break,
-    Continue,
-    Return { returned: Box<Statement> }
+    IfElse { condition: Expression, if_block: Box<Statement>, else_block: Option<Box<Statement>> },
+    /// `label` is `'outer` in `'outer: loop { ... }`, the same for other loops
+    Loop { label: Option<Token>, block: Box<Statement> },
+    For { label: Option<Token>, pattern: Pattern, container: Box<Statement>, block: Box<Statement> },
+    While { label: Option<Token>, condition: Expression, block: Box<Statement> },
+    DoWhile { label: Option<Token>, block: Box<Statement>, condition: Expression },
+    /// `break 'outer value;`, only `loop` can be left with a value
+    Break { label: Option<Token>, value: Option<Expression> },
+    Continue { label: Option<Token> },
+    /// `returned` is `None` for `return;`
+    Return { returned: Option<Expression> },
+    Enum { visibility: Visibility, name: Token, variants: Vec<Variant> },
+    Interface { visibility: Visibility, name: Token, methods: Vec<InterfaceMethod> },
+    /// `module math.vector;` names module of file
+    Module { path: Vec<Token> },
+    /// `import math.vector;` imports all declarations of module, `import { a, b } from util;` only listed ones
+    Import { path: Vec<Token>, names: Vec<Token> },
+    /// `fields` are `Let` and `methods` are `Function` statements
+    Class { attrs: Vec<Attribute>, visibility: Visibility, name: Token, generics: Vec<TypeParameter>, interfaces: Vec<Token>, fields: Vec<Statement>, methods: Vec<Statement> },
 }
 
 impl Statement {
-    pub fn to_string(&self) -> String {
+    /// Returns attributes of function, `let` or class, other statements can't have them
+    pub fn attrs(&self) -> &[Attribute] {
         match self {
+            Statement::Let { attrs, .. } | Statement::Function { attrs, .. } | Statement::Class { attrs, .. } => attrs,
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
             Statement::Block { statements } => {
                 let mut result = "(block \n".to_string();
                 for statement in statements {
@@ -42,14 +178,29 @@ impl Statement {
                     result.push('\n');
                 }
                 result.push(')');
-                return result;
+                result
             }
-            Statement::Let { mutable, defined, _type, name, value } => {
+            Statement::Let { attrs, visibility, mutable, _type, pattern, value } => {
                 format!(
-                    "(let{} {}: {} = {})",
+                    "({}{}let{} {}{}{})",
+                    attrs_to_string(attrs),
+                    visibility,
+                    mutable.then_some(" mut").unwrap_or(""),
+                    pattern,
+                    _type.as_ref().map(|_type| format!(": {}", _type)).unwrap_or_default(),
+                    value.as_ref().map(|value| format!(" = {}", value.to_string())).unwrap_or_default()
+                )
+            }
+            Statement::Const { visibility, name, _type, value } => {
+                format!("({}const {}: {} = {})", visibility, name.to_string(), _type, value.to_string())
+            }
+            Statement::Static { visibility, mutable, name, _type, value } => {
+                format!(
+                    "({}static{} {}: {} = {})",
+                    visibility,
                     mutable.then_some(" mut").unwrap_or(""),
                     name.to_string(),
-                    _type.to_string(),
+                    _type,
                     value.to_string()
                 )
             }
@@ -59,13 +210,16 @@ impl Statement {
                     expression.to_string()
                 )
             }
-            Statement::Function { name, _type, arguments, block } => {
+            Statement::Function { attrs, visibility, name, generics, _type, arguments, block } => {
                 format!(
-                    "(fun {}({:?}) -> {} {})",
+                    "({}{}fun {}{}({:?}) -> {} {})",
+                    attrs_to_string(attrs),
+                    visibility,
                     name.to_string(),
+                    generics_to_string(generics),
                     arguments,
-                    _type.to_string(),
-                    block.to_string()
+                    _type,
+                    block
                 )
             }
             Statement::FunctionUse { name, arguments } => {
@@ -81,62 +235,211 @@ impl Statement {
                         format!(
                             "(if ({}) {} else {})",
                             condition.to_string(),
-                            if_block.to_string(),
-                            block.to_string()
+                            if_block,
+                            block
                         )
                     }
                     None => {
                         format!(
                             "(if ({}) {})",
                             condition.to_string(),
-                            if_block.to_string()
+                            if_block
                         )
                     }
                 }
             }
-            Statement::Loop { block } => {
+            Statement::Loop { label, block } => {
                 format!(
-                    "(loop {})",
-                    block.to_string()
+                    "({}loop {})",
+                    label_to_string(label),
+                    block
                 )
             }
-            Statement::RangeIter { start_num, end_num } => {
+            Statement::For { label, pattern, container, block } => {
                 format!(
-                    "(range {}..{})",
-                    start_num.to_string(),
-                    end_num.to_string()
+                    "({}for ({} in {}) {})",
+                    label_to_string(label),
+                    pattern,
+                    container,
+                    block
                 )
             }
-            Statement::For { var, container, block } => {
+            Statement::While { label, condition, block } => {
                 format!(
-                    "(for ({} in {}) {})",
-                    var.to_string(),
-                    container.to_string(),
-                    block.to_string()
-                )
-            }
-            Statement::While { condition, block } => {
-                format!(
-                    "(while {} {})",
+                    "({}while {} {})",
+                    label_to_string(label),
                     condition.to_string(),
-                    block.to_string()
+                    block
                 )
             }
-            Statement::DoWhile { block, condition } => {
+            Statement::DoWhile { label, block, condition } => {
                 format!(
-                    "(do {} while {})",
-                    block.to_string(),
+                    "({}do {} while {})",
+                    label_to_string(label),
+                    block,
                     condition.to_string()
                 )
             }
-            Statement::Break => "(break)".to_string(),
-            Statement::Continue => "(continue)".to_string(),
+            Statement::Break { label, value } => {
+                let mut result = "(break".to_string();
+                if let Some(label) = label {
+                    result.push_str(&format!(" {}", label.lexeme));
+                }
+                if let Some(value) = value {
+                    result.push_str(&format!(" {}", value.to_string()));
+                }
+                result.push(')');
+                result
+            }
+            Statement::Continue { label } => {
+                match label {
+                    Some(label) => format!("(continue {})", label.lexeme),
+                    None => "(continue)".to_string(),
+                }
+            }
             Statement::Return { returned } => {
-                format!(
-                    "(return {})",
-                    returned.to_string()
-                )
+                match returned {
+                    Some(returned) => format!("(return {})", returned.to_string()),
+                    None => "(return)".to_string(),
+                }
+            }
+            Statement::Enum { visibility, name, variants } => {
+                let variants: Vec<String> = variants.iter()
+                    .map(|variant| format!("({}{})", variant.name.lexeme, variant.fields.to_string(|_type| _type.to_string())))
+                    .collect();
+                format!("({}enum {} {})", visibility, name.to_string(), variants.join(" "))
+            }
+            Statement::Interface { visibility, name, methods } => {
+                let mut result = format!("({}interface {}", visibility, name.to_string());
+                for method in methods {
+                    result.push_str(&format!(
+                        "\n(fun {}({:?}) -> {}{})",
+                        method.name.to_string(),
+                        method.arguments,
+                        method._type,
+                        method.block.as_ref().map(|block| format!(" {}", block)).unwrap_or_default()
+                    ));
+                }
+                result.push(')');
+                result
+            }
+            Statement::Module { path } => format!("(module {})", module_name(path)),
+            Statement::Import { path, names } => {
+                if names.is_empty() {
+                    return write!(f, "(import {})", module_name(path));
+                }
+                let names: Vec<String> = names.iter().map(|name| name.lexeme.clone()).collect();
+                format!("(import {{ {} }} from {})", names.join(", "), module_name(path))
             }
+            Statement::Class { attrs, visibility, name, generics, interfaces, fields, methods } => {
+                let interfaces: Vec<String> = interfaces.iter().map(|interface| interface.lexeme.clone()).collect();
+                let mut result = format!("({}{}class {}{}", attrs_to_string(attrs), visibility, name.to_string(), generics_to_string(generics));
+                if !interfaces.is_empty() {
+                    result.push_str(&format!(" : {}", interfaces.join(", ")));
+                }
+                for member in fields.iter().chain(methods) {
+                    result.push('\n');
+                    result.push_str(&member.to_string());
+                }
+                result.push(')');
+                result
+            }
+        };
+        f.write_str(&text)
+    }
+}
+
+/// Parsed file, items are in source order
+#[derive(Debug, PartialEq, Clone)]
+pub struct Program {
+    pub items: Vec<Item>,
+}
+
+/// Top level statement of file
+#[derive(Debug, PartialEq, Clone)]
+pub enum Item {
+    /// `module`, `import`, `const`, `static`, `fun`, `enum`, `interface` and `class`
+    Declaration { statement: Statement },
+    /// Statement which is executed when program runs, top level `let` too
+    Statement { statement: Statement },
+}
+
+impl Program {
+    /// Statements of all items, declarations are not separated from other statements
+    pub fn into_statements(self) -> Vec<Statement> {
+        self.items.into_iter().map(Item::into_statement).collect()
+    }
+
+    pub fn declarations(&self) -> Vec<&Statement> {
+        self.items.iter().filter(|item| item.is_declaration()).map(Item::statement).collect()
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "(program ")?;
+        for item in &self.items {
+            writeln!(f, "{}", item.statement())?;
         }
+        write!(f, ")")
+    }
+}
+
+impl Item {
+    pub fn new(statement: Statement) -> Self {
+        match statement {
+            Statement::Module { .. } | Statement::Import { .. } |
+            Statement::Const { .. } | Statement::Static { .. } |
+            Statement::Function { .. } | Statement::Enum { .. } |
+            Statement::Interface { .. } | Statement::Class { .. } => Item::Declaration { statement },
+            _ => Item::Statement { statement },
+        }
+    }
+
+    pub fn is_declaration(&self) -> bool {
+        matches!(self, Item::Declaration { .. })
+    }
+
+    pub fn statement(&self) -> &Statement {
+        match self {
+            Item::Declaration { statement } | Item::Statement { statement } => statement,
+        }
+    }
+
+    pub fn statement_mut(&mut self) -> &mut Statement {
+        match self {
+            Item::Declaration { statement } | Item::Statement { statement } => statement,
+        }
+    }
+
+    pub fn into_statement(self) -> Statement {
+        match self {
+            Item::Declaration { statement } | Item::Statement { statement } => statement,
+        }
+    }
+}
+
+/// Returns `#[test] #[inline] `, empty string if there are no attributes
+fn attrs_to_string(attrs: &[Attribute]) -> String {
+    attrs.iter().map(|attr| format!("{} ", attr)).collect()
+}
+
+/// Returns `'outer: ` for labeled loop, empty string if loop has no label
+pub fn label_to_string(label: &Option<Token>) -> String {
+    label.as_ref().map(|label| format!("{}: ", label.lexeme)).unwrap_or_default()
+}
+
+/// Returns `math.vector` for path of `module` or `import` declaration
+pub fn module_name(path: &[Token]) -> String {
+    let parts: Vec<String> = path.iter().map(|part| part.lexeme.clone()).collect();
+    parts.join(".")
+}
+
+/// Returns `<T: Ord, U>`, empty string if there are no type parameters
+fn generics_to_string(generics: &[TypeParameter]) -> String {
+    if generics.is_empty() {
+        return String::new();
     }
+    let generics: Vec<String> = generics.iter().map(|parameter| parameter.to_string()).collect();
+    format!("<{}>", generics.join(", "))
 }
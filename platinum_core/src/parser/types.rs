@@ -0,0 +1,68 @@
+use std::fmt;
+
+use crate::lexer::token::Token;
+
+use super::expr::Expression;
+
+/// Type written by user in annotation (`let a: [u8; 4]`, `fun a(b: u8)`)
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeExpr {
+    Named { name: Token },
+    /// `List<i32>`, `Result<i32, String>`
+    Generic { name: Token, arguments: Vec<TypeExpr> },
+    /// `i32?` can be `null`
+    Nullable { inner: Box<TypeExpr> },
+    /// `[u8; 4]` has size, dynamic array `[u8]` hasn't
+    Array { element: Box<TypeExpr>, size: Option<Box<Expression>> },
+    /// `#{String: i32}`
+    Map { key: Box<TypeExpr>, value: Box<TypeExpr> },
+    /// `(i32, String)`, `(i32,)` is a tuple with one element
+    Tuple { elements: Vec<TypeExpr> },
+    /// `fun(i32, i32) -> bool`
+    Function { arguments: Vec<TypeExpr>, returned: Box<TypeExpr> },
+}
+
+impl fmt::Display for TypeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeExpr::Named { name } => write!(f, "{}", name.to_string()),
+            TypeExpr::Generic { name, arguments } => {
+                let arguments: Vec<String> = arguments.iter().map(|argument| argument.to_string()).collect();
+                write!(f, "{}<{}>", name.to_string(), arguments.join(", "))
+            }
+            TypeExpr::Nullable { inner } => write!(f, "{}?", inner),
+            TypeExpr::Array { element, size } => {
+                match size {
+                    Some(size) => write!(f, "[{}; {}]", element, size.to_string()),
+                    None => write!(f, "[{}]", element)
+                }
+            }
+            TypeExpr::Map { key, value } => write!(f, "#{{{}: {}}}", key, value),
+            TypeExpr::Tuple { elements } => {
+                let elements: Vec<String> = elements.iter().map(|element| element.to_string()).collect();
+                write!(f, "({})", elements.join(", "))
+            }
+            TypeExpr::Function { arguments, returned } => {
+                let arguments: Vec<String> = arguments.iter().map(|argument| argument.to_string()).collect();
+                write!(f, "fun({}) -> {}", arguments.join(", "), returned)
+            }
+        }
+    }
+}
+
+/// Type parameter of generic function or class, `T: Ord + Eq`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeParameter {
+    pub name: Token,
+    pub bounds: Vec<Token>,
+}
+
+impl fmt::Display for TypeParameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.bounds.is_empty() {
+            return write!(f, "{}", self.name.lexeme);
+        }
+        let bounds: Vec<String> = self.bounds.iter().map(|bound| bound.lexeme.clone()).collect();
+        write!(f, "{}: {}", self.name.lexeme, bounds.join(" + "))
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::parser::{constants::substitute, expr::Expression, stmt::{Argument, Program, Statement}};
+
+use super::{manager::{check_items, Pass}, walk::{walk_all, walk_lengths, walk_statement}};
+
+/// Replaces expressions with their values when they are known without running program,
+/// `(38u8 + 24) - 95i16` becomes `-33`. Names of constants and immutable statics are replaced
+/// with their values in initializers of `const` and `static` and in lengths of arrays
+#[derive(Default)]
+pub struct Fold {
+    /// Folded values of constants and immutable statics declared before current item
+    constants: HashMap<String, Expression>,
+}
+
+impl Fold {
+    /// Knows values of constants which program imports from other files, they are already folded
+    pub fn with_imported(declarations: &[Statement]) -> Self {
+        let mut fold = Self::default();
+        for declaration in declarations {
+            fold.declare(declaration);
+        }
+        fold
+    }
+
+    fn declare(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Const { name, value, .. } |
+            Statement::Static { mutable: false, name, value, .. } => {
+                self.constants.insert(name.lexeme.clone(), value.clone());
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Pass for Fold {
+    fn name(&self) -> &'static str {
+        "fold"
+    }
+
+    fn run(&mut self, program: &mut Program) -> Result<(), String> {
+        check_items(program, |item| {
+            let statement = item.statement_mut();
+            // Local variables hide constants, parser checked that lengths of array types use only constants
+            let locals = local_names(statement)?;
+            let mut constants = self.constants.clone();
+            constants.retain(|name, _| !locals.contains(name));
+
+            if let Statement::Const { value, .. } | Statement::Static { value, .. } = statement {
+                *value = substitute(value, &constants);
+            }
+            walk_lengths(statement, &mut |length| {
+                *length = substitute(length, &constants);
+                Ok(())
+            })?;
+            walk_statement(statement, &mut |expression| {
+                *expression = expression.optimize_expression()?;
+                Ok(())
+            })?;
+            self.declare(statement);
+            Ok(())
+        })
+    }
+}
+
+/// Names of all variables and arguments declared inside of `statement`
+fn local_names(statement: &mut Statement) -> Result<HashSet<String>, String> {
+    let mut names = HashSet::new();
+    let mut expression_names = HashSet::new();
+    walk_all(statement, &mut |statement| {
+        match statement {
+            Statement::Let { pattern, .. } |
+            Statement::For { pattern, .. } => names.extend(pattern.bindings().into_iter().map(|name| name.lexeme)),
+            Statement::Function { arguments, .. } => declare_arguments(arguments, &mut names),
+            Statement::Interface { methods, .. } => {
+                for method in methods {
+                    declare_arguments(&method.arguments, &mut names);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }, &mut |expression| {
+        match expression {
+            Expression::Lambda { arguments, .. } => declare_arguments(arguments, &mut expression_names),
+            Expression::Match { arms, .. } => {
+                for arm in arms {
+                    expression_names.extend(arm.pattern.bindings().into_iter().map(|name| name.lexeme));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    })?;
+    names.extend(expression_names);
+    Ok(names)
+}
+
+fn declare_arguments(arguments: &[Argument], names: &mut HashSet<String>) {
+    for argument in arguments {
+        match argument {
+            Argument::NotOptional { name, .. } |
+            Argument::Optional { name, .. } => names.insert(name.lexeme.clone()),
+        };
+    }
+}
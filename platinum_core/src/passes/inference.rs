@@ -0,0 +1,774 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{lexer::token::{Token, TokenType}, parser::{expr::{Expression, Type}, infer::{join, ClassSignature, Signature, Substitution, Ty}, pattern::Pattern, stmt::{Argument, Statement, UseArgument, Variant, VariantFields, Visibility}, types::{TypeExpr, TypeParameter}}};
+
+/// Declarations which are visible in every scope of program
+#[derive(Debug, Default)]
+pub struct Declarations {
+    /// Variants of enums
+    pub enums: HashMap<String, Vec<Variant>>,
+    pub interfaces: HashSet<String>,
+    pub classes: HashMap<String, ClassSignature>,
+    /// Top level functions, calls to them are checked
+    pub functions: HashMap<String, Signature>,
+    /// Types of imported variables, constants and statics
+    pub variables: HashMap<String, Ty>,
+}
+
+impl Declarations {
+    /// Remembers enum, interface or class, members of `imported` classes can be used only if they are `pub`
+    pub fn declare_type(&mut self, statement: &Statement, imported: bool) {
+        match statement {
+            Statement::Enum { name, variants, .. } => {
+                self.enums.insert(name.lexeme.clone(), variants.clone());
+            }
+            Statement::Interface { name, .. } => {
+                self.interfaces.insert(name.lexeme.clone());
+            }
+            Statement::Class { name, generics, interfaces, fields, methods, .. } => {
+                let mut members = vec![];
+                for member in fields.iter().chain(methods) {
+                    match member {
+                        Statement::Let { pattern, visibility, .. } => {
+                            members.extend(pattern.bindings().into_iter().map(|name| (name, *visibility)));
+                        }
+                        Statement::Function { name, visibility, .. } => members.push((name.clone(), *visibility)),
+                        _ => {}
+                    }
+                }
+                self.classes.insert(name.lexeme.clone(), ClassSignature {
+                    generics: generics.clone(),
+                    interfaces: interfaces.clone(),
+                    members,
+                    imported
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Remembers function, constant, static or variable declared on top level of imported module
+    pub fn declare_imported(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Function { name, generics, arguments, _type, .. } => {
+                self.functions.insert(name.lexeme.clone(), signature(generics, arguments, _type));
+            }
+            Statement::Const { name, _type, .. } |
+            Statement::Static { name, _type, .. } => {
+                self.variables.insert(name.lexeme.clone(), Ty::from_type(_type, &vec![]));
+            }
+            Statement::Let { pattern, _type, value, .. } => {
+                let variable_type = match (_type, value) {
+                    (Some(_type), _) => Ty::from_type(_type, &vec![]),
+                    (None, Some(value)) => Inference::new(self).infer(value).unwrap_or(Ty::Unknown).with_default_literals(),
+                    (None, None) => Ty::Unknown,
+                };
+                for name in pattern.bindings() {
+                    let _type = if let Pattern::Binding { .. } = pattern { variable_type.clone() } else { Ty::Unknown };
+                    self.variables.insert(name.lexeme.clone(), _type);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Loop which values of `break` are joined
+struct LoopTypes {
+    label: Option<String>,
+    /// Type of values given by `break`s checked so far
+    value_type: Option<Ty>,
+}
+
+/// Infers types of values walking statements in order they are written, so variables declared
+/// before value are known. Checks values stored in places with type annotations, conditions,
+/// calls of declared functions, branches of `if` and values of `break`
+pub struct Inference<'a> {
+    declarations: &'a Declarations,
+    /// Types of variables in scopes which contain current statement, the first scope is top level of file
+    variables: Vec<HashMap<String, Ty>>,
+    /// Functions declared in blocks and methods of classes
+    functions: Vec<HashMap<String, Signature>>,
+    /// Type parameters of functions and classes which contain current statement
+    type_parameters: Vec<TypeParameter>,
+    /// Class which body contains current statement, its private members can be used
+    current_class: Option<String>,
+    loops: Vec<LoopTypes>,
+}
+
+impl<'a> Inference<'a> {
+    pub fn new(declarations: &'a Declarations) -> Self {
+        Self {
+            declarations,
+            variables: vec![declarations.variables.clone()],
+            functions: vec![],
+            type_parameters: vec![],
+            current_class: None,
+            loops: vec![],
+        }
+    }
+
+    /// Checks top level statement, variables declared by it stay in scope for the next ones
+    pub fn check_item(&mut self, statement: &Statement) -> Result<(), String> {
+        let result = self.statement(statement);
+        if result.is_err() {
+            // Failed declaration can leave its scopes open
+            self.variables.truncate(1);
+            self.functions.clear();
+            self.type_parameters.clear();
+            self.current_class = None;
+            self.loops.clear();
+        }
+        result
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Result<(), String> {
+        match statement {
+            Statement::Block { statements } => {
+                self.variables.push(HashMap::new());
+                self.functions.push(HashMap::new());
+                for statement in statements {
+                    self.statement(statement)?;
+                }
+                self.functions.pop();
+                self.variables.pop();
+            }
+            Statement::Assigment { expression } => {
+                self.infer(expression)?;
+            }
+            Statement::Let { _type, pattern, value, .. } => {
+                if let Some(_type) = _type {
+                    self.check_annotation(_type)?;
+                }
+                let mut variable_type = _type.as_ref().map(|_type| Ty::from_type(_type, &vec![]));
+                if let Some(value) = value {
+                    let found = self.infer(value)?;
+                    match _type {
+                        Some(_type) => self.check_declared_value(_type, &found)?,
+                        None if found == Ty::Named("null".to_string()) => {
+                            let names: Vec<String> = pattern.bindings().iter().map(|name| name.lexeme.clone()).collect();
+                            return Err(format!("Type of `{}` can't be inferred from `null`, add type annotation", names.join(", ")));
+                        }
+                        None => variable_type = Some(found.with_default_literals()),
+                    }
+                }
+                for name in pattern.bindings() {
+                    let _type = match (pattern, &variable_type) {
+                        (Pattern::Binding { .. }, Some(_type)) => _type.clone(),
+                        _ => Ty::Unknown,
+                    };
+                    self.declare_variable(&name, _type);
+                }
+            }
+            Statement::Const { name, _type, value, .. } |
+            Statement::Static { name, _type, value, .. } => {
+                self.check_annotation(_type)?;
+                let found = self.infer(value)?;
+                self.check_declared_value(_type, &found)?;
+                self.declare_variable(name, Ty::from_type(_type, &vec![]));
+            }
+            Statement::Function { name, generics, arguments, _type, block, .. } => {
+                if let Some(scope) = self.functions.last_mut() {
+                    scope.insert(name.lexeme.clone(), signature(generics, arguments, _type));
+                }
+                let outer_parameters = self.type_parameters.len();
+                self.type_parameters.extend(generics.iter().cloned());
+                let result = self.function(arguments, Some(_type), block);
+                self.type_parameters.truncate(outer_parameters);
+                result?;
+            }
+            Statement::FunctionUse { name, arguments } => {
+                let found = self.infer_arguments(arguments)?;
+                self.check_call(name, &[], arguments, &found)?;
+            }
+            Statement::IfElse { condition, if_block, else_block } => {
+                self.condition(condition, "if")?;
+                self.statement(if_block)?;
+                if let Some(else_block) = else_block {
+                    self.statement(else_block)?;
+                }
+            }
+            Statement::Loop { label, block } => {
+                self.loop_body(label, |inference| inference.statement(block))?;
+            }
+            Statement::For { label, pattern, container, block } => {
+                self.statement(container)?;
+                self.variables.push(pattern.bindings().iter().map(|name| (name.lexeme.clone(), Ty::Unknown)).collect());
+                let result = self.loop_body(label, |inference| inference.statement(block));
+                self.variables.pop();
+                result?;
+            }
+            Statement::While { label, condition, block } => {
+                self.condition(condition, "while")?;
+                self.loop_body(label, |inference| inference.statement(block))?;
+            }
+            Statement::DoWhile { label, block, condition } => {
+                self.loop_body(label, |inference| inference.statement(block))?;
+                self.condition(condition, "while")?;
+            }
+            Statement::Break { label, value } => {
+                if let Some(value) = value {
+                    let value_type = self.infer(value)?;
+                    let target = match label {
+                        Some(label) => self.loops.iter_mut().rev().find(|target| target.label.as_ref() == Some(&label.lexeme)),
+                        None => self.loops.last_mut(),
+                    };
+                    if let Some(target) = target {
+                        let value_type = match &target.value_type {
+                            Some(previous) => join(previous, &value_type).map_err(|msg| format!("`break` values have incompatible types: {}", msg))?,
+                            None => value_type,
+                        };
+                        target.value_type = Some(value_type);
+                    }
+                }
+            }
+            Statement::Return { returned } => {
+                if let Some(returned) = returned {
+                    self.infer(returned)?;
+                }
+            }
+            Statement::Enum { variants, .. } => {
+                for variant in variants {
+                    match &variant.fields {
+                        VariantFields::Unit => {}
+                        VariantFields::Tuple { values } => {
+                            for _type in values {
+                                self.check_annotation(_type)?;
+                            }
+                        }
+                        VariantFields::Struct { fields } => {
+                            for (_, _type) in fields {
+                                self.check_annotation(_type)?;
+                            }
+                        }
+                    }
+                }
+            }
+            Statement::Interface { methods, .. } => {
+                for method in methods {
+                    match &method.block {
+                        Some(block) => self.function(&method.arguments, Some(&method._type), block)?,
+                        None => {
+                            self.check_arguments(&method.arguments)?;
+                            self.check_annotation(&method._type)?;
+                        }
+                    }
+                }
+            }
+            Statement::Class { name, generics, fields, methods, .. } => {
+                let outer_class = self.current_class.replace(name.lexeme.clone());
+                let outer_parameters = self.type_parameters.len();
+                self.type_parameters.extend(generics.iter().cloned());
+                self.variables.push(HashMap::new());
+                // Methods are not visible outside of class
+                self.functions.push(HashMap::new());
+                let result = fields.iter().chain(methods).try_for_each(|member| self.statement(member));
+                self.functions.pop();
+                self.variables.pop();
+                self.type_parameters.truncate(outer_parameters);
+                self.current_class = outer_class;
+                result?;
+            }
+            Statement::Continue { .. } |
+            Statement::Module { .. } |
+            Statement::Import { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Checks arguments and body of function, method or lambda
+    fn function(&mut self, arguments: &[Argument], _type: Option<&TypeExpr>, body: &Statement) -> Result<(), String> {
+        self.check_arguments(arguments)?;
+        if let Some(_type) = _type {
+            self.check_annotation(_type)?;
+        }
+        self.variables.push(argument_types(arguments));
+        // `break` can't leave loop around function
+        let loops = std::mem::take(&mut self.loops);
+        let result = self.statement(body);
+        self.loops = loops;
+        self.variables.pop();
+        result
+    }
+
+    /// Checks annotations of arguments and values of optional ones
+    fn check_arguments(&mut self, arguments: &[Argument]) -> Result<(), String> {
+        for argument in arguments {
+            match argument {
+                Argument::NotOptional { _type, .. } => self.check_annotation(_type)?,
+                Argument::Optional { _type, value, .. } => {
+                    self.check_annotation(_type)?;
+                    let found = self.infer(value)?;
+                    self.check_declared_value(_type, &found)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks body of loop, its `break`s give values of type which is returned
+    fn loop_body(&mut self, label: &Option<Token>, body: impl FnOnce(&mut Self) -> Result<(), String>) -> Result<Ty, String> {
+        self.loops.push(LoopTypes { label: label.as_ref().map(|label| label.lexeme.clone()), value_type: None });
+        let result = body(self);
+        let target = self.loops.pop();
+        result?;
+        Ok(target.and_then(|target| target.value_type).unwrap_or(Ty::Unknown))
+    }
+
+    /// Condition of `if` or `while` should be `bool` if its type is known
+    fn condition(&mut self, condition: &Expression, keyword: &str) -> Result<(), String> {
+        let found = self.infer(condition)?;
+        if found != Ty::Unknown && found != Ty::Named("bool".to_string()) {
+            return Err(format!("Condition of `{}` should be `bool`, found `{}`", keyword, found.name()));
+        }
+        Ok(())
+    }
+
+    /// Checks that inferred type `found` can be stored in place with `_type` annotation
+    fn check_declared_value(&self, _type: &TypeExpr, found: &Ty) -> Result<(), String> {
+        let expected = Ty::from_type(_type, &vec![]);
+        if Substitution::new().unify(&expected, found).is_err() {
+            return Err(format!("Expected `{}`, found `{}`", expected.name(), found.name()));
+        }
+        Ok(())
+    }
+
+    /// Checks that generic classes in annotation get as many type arguments as they declare
+    /// and the arguments implement bounds of type parameters
+    fn check_annotation(&self, _type: &TypeExpr) -> Result<(), String> {
+        match _type {
+            TypeExpr::Named { .. } => {}
+            TypeExpr::Generic { name, arguments } => {
+                for argument in arguments {
+                    self.check_annotation(argument)?;
+                }
+                if let Some(class) = self.declarations.classes.get(&name.lexeme) {
+                    if class.generics.len() != arguments.len() {
+                        return Err(format!("Class `{}` takes {} type arguments, found {}", name.lexeme, class.generics.len(), arguments.len()));
+                    }
+                    for (parameter, argument) in class.generics.iter().zip(arguments) {
+                        self.check_bounds(parameter, &Ty::from_type(argument, &vec![]), name)?;
+                    }
+                }
+            }
+            TypeExpr::Tuple { elements } => {
+                for element in elements {
+                    self.check_annotation(element)?;
+                }
+            }
+            TypeExpr::Nullable { inner } => self.check_annotation(inner)?,
+            TypeExpr::Array { element, .. } => self.check_annotation(element)?,
+            TypeExpr::Map { key, value } => {
+                self.check_annotation(key)?;
+                self.check_annotation(value)?;
+            }
+            TypeExpr::Function { arguments, returned } => {
+                for argument in arguments {
+                    self.check_annotation(argument)?;
+                }
+                self.check_annotation(returned)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns type of `value`, `Unknown` if it depends on fields or values which aren't checked.
+    /// Everything inside of `value` is checked too
+    pub fn infer(&mut self, value: &Expression) -> Result<Ty, String> {
+        let named = |name: &str| Ty::Named(name.to_string());
+        match value {
+            Expression::Literal { value } => {
+                Ok(match &value.token_type {
+                    TokenType::Int { num_type, .. } | TokenType::Float { num_type, .. } => Ty::from_number(*num_type),
+                    TokenType::StringT { .. } => named("String"),
+                    TokenType::Char { .. } => named("char"),
+                    TokenType::BoolT { .. } => named("bool"),
+                    TokenType::Null => named("null"),
+                    _ => Ty::Unknown,
+                })
+            }
+            Expression::Grouping { expression } => self.infer(expression),
+            Expression::Variable { name } => Ok(self.variable_type(name)),
+            Expression::EnumVariant { enum_name, fields, .. } => {
+                match fields {
+                    VariantFields::Unit => {}
+                    VariantFields::Tuple { values } => {
+                        for value in values {
+                            self.infer(value)?;
+                        }
+                    }
+                    VariantFields::Struct { fields } => {
+                        for (_, value) in fields {
+                            self.infer(value)?;
+                        }
+                    }
+                }
+                Ok(named(&enum_name.lexeme))
+            }
+            Expression::Call { callee, type_arguments, arguments } => {
+                for _type in type_arguments {
+                    self.check_annotation(_type)?;
+                }
+                let found = self.infer_arguments(arguments)?;
+                match &**callee {
+                    Expression::Variable { name } => self.check_call(name, type_arguments, arguments, &found),
+                    callee => {
+                        self.infer(callee)?;
+                        Ok(Ty::Unknown)
+                    }
+                }
+            }
+            Expression::Tuple { elements } => {
+                let mut types = vec![];
+                for element in elements {
+                    types.push(self.infer(element)?);
+                }
+                Ok(Ty::Tuple(types))
+            }
+            Expression::Array { elements } => {
+                let mut types = vec![];
+                for element in elements {
+                    types.push(self.infer(element)?);
+                }
+                // Type of the first element which is known
+                let element = types.into_iter().find(|_type| *_type != Ty::Unknown).unwrap_or(Ty::Unknown);
+                Ok(Ty::Array(Box::from(element)))
+            }
+            Expression::Block { statements, value } => {
+                self.variables.push(HashMap::new());
+                self.functions.push(HashMap::new());
+                let result = self.block(statements, value);
+                self.functions.pop();
+                self.variables.pop();
+                result
+            }
+            Expression::If { condition, then_branch, else_branch } => {
+                self.condition(condition, "if")?;
+                let then_type = self.infer(then_branch)?;
+                let Some(else_branch) = else_branch else {
+                    return Ok(Ty::Unknown);
+                };
+                let else_type = self.infer(else_branch)?;
+                join(&then_type, &else_type).map_err(|msg| format!("`if` and `else` have incompatible types: {}", msg))
+            }
+            Expression::Loop { label, body } => self.loop_body(label, |inference| inference.infer(body).map(|_| ())),
+            Expression::Lambda { arguments, _type, body, .. } => {
+                self.function(arguments, _type.as_ref(), body)?;
+                let arguments = arguments.iter()
+                    .map(|argument| match argument {
+                        Argument::NotOptional { _type, .. } |
+                        Argument::Optional { _type, .. } => Ty::from_type(_type, &vec![]),
+                    })
+                    .collect();
+                let returned = _type.as_ref().map(|_type| Ty::from_type(_type, &vec![])).unwrap_or(Ty::Unknown);
+                Ok(Ty::Function(arguments, Box::from(returned)))
+            }
+            Expression::Binary { left, operator, right } => {
+                let (left, right) = (self.infer(left)?, self.infer(right)?);
+                // Operands which can't be used together are reported by `check_and_get_type`
+                Ok(match (left.to_checked(), right.to_checked()) {
+                    (Type::String, _) if matches!(operator.token_type, TokenType::Plus) => named("String"),
+                    (Type::Integer, Type::Integer) |
+                    (Type::Float, Type::Float) => join(&left, &right).unwrap_or(Ty::Unknown),
+                    (Type::Integer, Type::Float) => right,
+                    (Type::Float, Type::Integer) => left,
+                    _ => Ty::Unknown,
+                })
+            }
+            Expression::Unary { right, .. } => {
+                let right = self.infer(right)?;
+                match right.to_checked() {
+                    Type::Integer | Type::Float | Type::Bool => Ok(right),
+                    _ => Ok(Ty::Unknown),
+                }
+            }
+            Expression::Ternary { result, true_expression, false_expression } => {
+                self.infer(result)?;
+                join(&self.infer(true_expression)?, &self.infer(false_expression)?)
+            }
+            Expression::EqualtyComparison { left, right, .. } |
+            Expression::Logical { left, right, .. } => {
+                self.infer(left)?;
+                self.infer(right)?;
+                Ok(named("bool"))
+            }
+            Expression::NullCoalescing { left, right, .. } |
+            Expression::Index { object: left, index: right } |
+            Expression::ArrayRepeat { value: left, count: right } => {
+                self.infer(left)?;
+                self.infer(right)?;
+                Ok(Ty::Unknown)
+            }
+            Expression::Assign { target, operator, value } => {
+                let expected = self.infer(target)?;
+                let found = self.infer(value)?;
+                if operator.token_type == TokenType::Equal && Substitution::new().unify(&expected, &found).is_err() {
+                    return Err(format!("Expected `{}`, found `{}`", expected.name(), found.name()));
+                }
+                if !matches!(operator.token_type, TokenType::Equal | TokenType::QuestionQuestionEqual) && !compound_allowed(operator, &expected, &found) {
+                    return Err(format!("Can't use operator `{}` with `{}` and `{}`", operator.lexeme, expected.name(), found.name()));
+                }
+                Ok(Ty::Unknown)
+            }
+            Expression::Increment { target, .. } |
+            Expression::TupleIndex { object: target, .. } => {
+                self.infer(target)?;
+                Ok(Ty::Unknown)
+            }
+            Expression::Get { object, name, .. } => {
+                self.infer(object)?;
+                if let Expression::Variable { name: variable } = &**object {
+                    self.check_member_access(variable, name)?;
+                }
+                Ok(Ty::Unknown)
+            }
+            Expression::Map { entries } => {
+                for (key, value) in entries {
+                    self.infer(key)?;
+                    self.infer(value)?;
+                }
+                Ok(Ty::Unknown)
+            }
+            Expression::Range { start, end, .. } => {
+                for bound in [start, end].into_iter().flatten() {
+                    self.infer(bound)?;
+                }
+                Ok(Ty::Unknown)
+            }
+            Expression::Match { value, arms } => {
+                self.infer(value)?;
+                for arm in arms {
+                    self.variables.push(arm.pattern.bindings().iter().map(|name| (name.lexeme.clone(), Ty::Unknown)).collect());
+                    let result = self.match_arm(&arm.guard, &arm.body);
+                    self.variables.pop();
+                    result?;
+                }
+                Ok(Ty::Unknown)
+            }
+        }
+    }
+
+    /// Checks statements of block and returns type of its value, variables declared in block are in scope
+    fn block(&mut self, statements: &[Statement], value: &Option<Box<Expression>>) -> Result<Ty, String> {
+        for statement in statements {
+            self.statement(statement)?;
+        }
+        match value {
+            Some(value) => self.infer(value),
+            None => Ok(Ty::Unknown),
+        }
+    }
+
+    fn match_arm(&mut self, guard: &Option<Expression>, body: &Statement) -> Result<(), String> {
+        if let Some(guard) = guard {
+            self.infer(guard)?;
+        }
+        self.statement(body)
+    }
+
+    fn infer_arguments(&mut self, arguments: &[UseArgument]) -> Result<Vec<Ty>, String> {
+        let mut types = vec![];
+        for argument in arguments {
+            match argument {
+                UseArgument::Expr { value } |
+                UseArgument::Optional { value, .. } => types.push(self.infer(value)?),
+            }
+        }
+        Ok(types)
+    }
+
+    /// Returns declared function with `name`, functions of inner scopes hide outer ones
+    fn function_signature(&self, name: &str) -> Option<&Signature> {
+        self.functions.iter().rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.declarations.functions.get(name))
+    }
+
+    /// Checks call of declared function and returns type of its result, type arguments are inferred if not written.
+    /// `found` are types of `arguments`
+    fn check_call(&self, name: &Token, type_arguments: &[TypeExpr], arguments: &[UseArgument], found: &[Ty]) -> Result<Ty, String> {
+        let Some(signature) = self.function_signature(&name.lexeme) else {
+            return Ok(Ty::Unknown);
+        };
+        let parameters = signature.parameter_names();
+        let mut substitution = Substitution::new();
+        if !type_arguments.is_empty() {
+            if type_arguments.len() != parameters.len() {
+                return Err(format!(
+                    "Function `{}` takes {} type arguments, found {}",
+                    name.lexeme,
+                    parameters.len(),
+                    type_arguments.len()
+                ));
+            }
+            for (parameter, argument) in parameters.iter().zip(type_arguments) {
+                substitution.bind(parameter, Ty::from_type(argument, &vec![]));
+            }
+        }
+
+        let required = signature.arguments.iter().filter(|argument| matches!(argument, Argument::NotOptional { .. })).count();
+        let positional = arguments.iter().filter(|argument| matches!(argument, UseArgument::Expr { .. })).count();
+        if positional < required || positional > signature.arguments.len() {
+            let expected = match required == signature.arguments.len() {
+                true => required.to_string(),
+                false => format!("{} to {}", required, signature.arguments.len()),
+            };
+            return Err(format!("Function `{}` takes {} arguments, found {}", name.lexeme, expected, positional));
+        }
+        for (position, (argument, found)) in arguments.iter().zip(found).enumerate() {
+            let declared = match argument {
+                UseArgument::Expr { .. } => &signature.arguments[position],
+                UseArgument::Optional { name: argument_name, .. } => {
+                    let declared = signature.arguments.iter().find(|declared| {
+                        matches!(declared, Argument::Optional { name, .. } if name.lexeme == argument_name.lexeme)
+                    });
+                    match declared {
+                        Some(declared) => declared,
+                        None => return Err(format!("Function `{}` has no optional argument `{}`", name.lexeme, argument_name.lexeme)),
+                    }
+                }
+            };
+            let (Argument::NotOptional { name: argument_name, _type } | Argument::Optional { name: argument_name, _type, .. }) = declared;
+            let expected = Ty::from_type(_type, &parameters);
+            if substitution.unify(&expected, found).is_err() {
+                return Err(format!(
+                    "Argument `{}` of `{}` should be `{}`, found `{}`",
+                    argument_name.lexeme,
+                    name.lexeme,
+                    substitution.apply(&expected).name(),
+                    found.name()
+                ));
+            }
+            if let Ty::Var(parameter) = &expected {
+                // `max(1, 2i64)` makes `T` an `i64`, not an untyped integer
+                substitution.refine(parameter, found);
+            }
+        }
+
+        for parameter in &signature.generics {
+            let _type = substitution.apply(&Ty::Var(parameter.name.lexeme.clone()));
+            if let Ty::Var(_) = _type {
+                // Nothing is known about parameter, for example all arguments are variables
+                substitution.bind(&parameter.name.lexeme, Ty::Unknown);
+                continue;
+            }
+            self.check_bounds(parameter, &_type, name)?;
+        }
+        Ok(substitution.apply(&Ty::from_type(&signature._type, &parameters)))
+    }
+
+    /// Checks that `_type` used for type parameter of `owner` implements all bounds of the parameter
+    fn check_bounds(&self, parameter: &TypeParameter, _type: &Ty, owner: &Token) -> Result<(), String> {
+        for bound in &parameter.bounds {
+            if self.implements(_type, &bound.lexeme) == Some(false) {
+                return Err(format!(
+                    "Type `{}` doesn't implement `{}` required by `{}` of `{}`",
+                    _type.name(),
+                    bound.lexeme,
+                    parameter.name.lexeme,
+                    owner.lexeme
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if values of `_type` implement interface `bound`, `None` if it isn't known
+    fn implements(&self, _type: &Ty, bound: &str) -> Option<bool> {
+        let name = match _type {
+            Ty::Named(name) | Ty::Generic(name, _) => name,
+            _ => return None,
+        };
+        if let Some(parameter) = self.type_parameters.iter().rev().find(|parameter| &parameter.name.lexeme == name) {
+            return Some(parameter.bounds.iter().any(|other| other.lexeme == bound));
+        }
+        if let Some(class) = self.declarations.classes.get(name) {
+            return Some(class.interfaces.iter().any(|interface| interface.lexeme == bound));
+        }
+        if self.declarations.interfaces.contains(name) {
+            return Some(name == bound);
+        }
+        if self.declarations.enums.contains_key(name) {
+            return Some(false);
+        }
+        if _type.is_primitive() {
+            return match bound {
+                "Eq" => Some(true),
+                "Ord" => Some(name != "bool"),
+                "Num" => Some(_type.is_number()),
+                _ => Some(false),
+            };
+        }
+        None
+    }
+
+    fn declare_variable(&mut self, name: &Token, _type: Ty) {
+        if let Some(scope) = self.variables.last_mut() {
+            scope.insert(name.lexeme.clone(), _type);
+        }
+    }
+
+    /// Returns declared type of variable, `Unknown` if it isn't known
+    fn variable_type(&self, name: &Token) -> Ty {
+        self.variables.iter().rev()
+            .find_map(|scope| scope.get(&name.lexeme))
+            .cloned()
+            .unwrap_or(Ty::Unknown)
+    }
+
+    /// Checks that `member` of object in `variable` can be used here
+    fn check_member_access(&self, variable: &Token, member: &Token) -> Result<(), String> {
+        let class_name = match self.variable_type(variable) {
+            Ty::Named(name) | Ty::Generic(name, _) => name,
+            Ty::Nullable(inner) => match *inner {
+                Ty::Named(name) | Ty::Generic(name, _) => name,
+                _ => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        let Some(class) = self.declarations.classes.get(&class_name) else {
+            return Ok(());
+        };
+        let Some((_, visibility)) = class.members.iter().find(|(name, _)| name.lexeme == member.lexeme) else {
+            return Ok(());
+        };
+        match visibility {
+            Visibility::Private if self.current_class.as_ref() != Some(&class_name) => {
+                Err(format!("`{}` is private in class `{}`", member.lexeme, class_name))
+            }
+            Visibility::Module if class.imported => {
+                Err(format!("`{}` of class `{}` isn't `pub` and can't be used outside of its module", member.lexeme, class_name))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn signature(generics: &[TypeParameter], arguments: &[Argument], _type: &TypeExpr) -> Signature {
+    Signature { generics: generics.to_vec(), arguments: arguments.to_vec(), _type: _type.clone() }
+}
+
+/// Returns types of arguments for scope of function or lambda body
+fn argument_types(arguments: &[Argument]) -> HashMap<String, Ty> {
+    arguments.iter()
+        .map(|argument| match argument {
+            Argument::NotOptional { name, _type } |
+            Argument::Optional { name, _type, .. } => (name.lexeme.clone(), Ty::from_type(_type, &vec![])),
+        })
+        .collect()
+}
+
+/// Returns true if compound assignment `operator` can change place of `target` type with `value`
+fn compound_allowed(operator: &Token, target: &Ty, value: &Ty) -> bool {
+    let arithmetic = matches!(
+        operator.token_type,
+        TokenType::PlusEqual | TokenType::MinusEqual | TokenType::StarEqual | TokenType::SlashEqual | TokenType::PersentEqual
+    );
+    match (target.to_checked(), value.to_checked()) {
+        (Type::Unknown, _) => true,
+        (Type::String, _) => operator.token_type == TokenType::PlusEqual,
+        (Type::Integer, Type::Integer | Type::Unknown) => true,
+        (Type::Float, Type::Integer | Type::Float | Type::Unknown) => arithmetic,
+        _ => false,
+    }
+}
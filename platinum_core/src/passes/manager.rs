@@ -0,0 +1,65 @@
+use crate::parser::stmt::{Item, Program, Statement};
+
+use super::{fold::Fold, type_check::TypeCheck};
+
+/// Step which checks or transforms parsed program
+pub trait Pass {
+    fn name(&self) -> &'static str;
+    /// Returns all errors found in program joined by `\n`
+    fn run(&mut self, program: &mut Program) -> Result<(), String>;
+}
+
+/// Runs passes over program in order they were added, stops after first failed pass
+pub struct PassManager {
+    passes: Vec<Box<dyn Pass>>,
+}
+
+impl PassManager {
+    /// Manager without passes, program is left as it was parsed
+    pub fn new() -> Self {
+        Self { passes: vec![] }
+    }
+
+    /// Type checking and constant folding, `Parser::parse` runs these passes.
+    /// `Resolve` isn't one of them, names imported by `import module;` without list
+    /// aren't declared in program and would be reported as undeclared
+    pub fn standard() -> Self {
+        Self::standard_with(&[])
+    }
+
+    /// Standard passes which know `imported` declarations from other files
+    pub fn standard_with(imported: &[Statement]) -> Self {
+        Self::new().with(TypeCheck::with_imported(imported)).with(Fold::with_imported(imported))
+    }
+
+    pub fn with(mut self, pass: impl Pass + 'static) -> Self {
+        self.passes.push(Box::new(pass));
+        self
+    }
+
+    pub fn pass_names(&self) -> Vec<&'static str> {
+        self.passes.iter().map(|pass| pass.name()).collect()
+    }
+
+    pub fn run(&mut self, program: &mut Program) -> Result<(), String> {
+        for pass in self.passes.iter_mut() {
+            pass.run(program)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for PassManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs `check` for every item, errors of items are collected instead of stopping at the first one
+pub fn check_items(program: &mut Program, mut check: impl FnMut(&mut Item) -> Result<(), String>) -> Result<(), String> {
+    let errors: Vec<String> = program.items.iter_mut().filter_map(|item| check(item).err()).collect();
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    Ok(())
+}
@@ -0,0 +1,6 @@
+pub mod manager;
+pub mod walk;
+pub mod resolve;
+pub mod inference;
+pub mod type_check;
+pub mod fold;
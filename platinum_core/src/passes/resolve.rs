@@ -0,0 +1,39 @@
+use crate::parser::{captures::find_captures, stmt::{Program, Statement}};
+
+use super::manager::Pass;
+
+/// Checks that every used name is declared, top level declarations can be used before them.
+/// Names brought by `import module;` without list aren't known here and are reported too
+pub struct Resolve;
+
+impl Pass for Resolve {
+    fn name(&self) -> &'static str {
+        "resolve"
+    }
+
+    fn run(&mut self, program: &mut Program) -> Result<(), String> {
+        let mut declared = vec![];
+        for item in program.declarations() {
+            match item {
+                Statement::Const { name, .. } |
+                Statement::Static { name, .. } |
+                Statement::Function { name, .. } |
+                Statement::Enum { name, .. } |
+                Statement::Interface { name, .. } |
+                Statement::Class { name, .. } => declared.push(name.lexeme.clone()),
+                Statement::Import { names, .. } => declared.extend(names.iter().map(|name| name.lexeme.clone())),
+                _ => {}
+            }
+        }
+        let statements = program.items.iter().map(|item| item.statement().clone()).collect();
+        let errors: Vec<String> = find_captures(&vec![], &Statement::Block { statements })
+            .into_iter()
+            .filter(|capture| !declared.contains(&capture.name.lexeme))
+            .map(|capture| format!("Undeclared name `{}` at possition {:?}", capture.name.lexeme, capture.name.possition))
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+        Ok(())
+    }
+}
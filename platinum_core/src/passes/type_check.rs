@@ -0,0 +1,173 @@
+use crate::parser::{exhaustiveness::check_arms, expr::{Expression, Type}, infer::Ty, pattern::Pattern, stmt::{Program, Statement, Variant, VariantFields}, types::TypeExpr};
+
+use super::{inference::{signature, Declarations, Inference}, manager::{check_items, Pass}, walk::{walk_all, walk_statement}};
+
+/// Checks types of operands in every expression of program, values stored in places with
+/// type annotations, calls of declared functions, fields of enum variants and patterns of `match`
+#[derive(Default)]
+pub struct TypeCheck {
+    /// Declarations of program are added to ones given in `with_imported`
+    declarations: Declarations,
+}
+
+impl TypeCheck {
+    /// Knows `declarations` which program imports from other files
+    pub fn with_imported(declarations: &[Statement]) -> Self {
+        let mut type_check = Self::default();
+        for declaration in declarations {
+            type_check.declarations.declare_type(declaration, true);
+            type_check.declarations.declare_imported(declaration);
+        }
+        type_check
+    }
+
+    fn check_statement(&self, statement: &Statement) -> Result<(), String> {
+        match statement {
+            Statement::Const { _type, value, .. } |
+            Statement::Static { _type, value, .. } => self.check_value_type(_type, value),
+            Statement::Let { _type, pattern, value, .. } => {
+                if let Some(_type) = _type {
+                    pattern.check_type(_type)?;
+                }
+                if let Some(value) = value {
+                    pattern.check_value(value)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_expression(&self, expression: &Expression) -> Result<(), String> {
+        match expression {
+            Expression::EnumVariant { enum_name, variant, fields } => {
+                let Some(declared) = self.find_variant(&enum_name.lexeme, &variant.lexeme) else {
+                    return Ok(());
+                };
+                match (&declared.fields, fields) {
+                    (VariantFields::Tuple { values: types }, VariantFields::Tuple { values }) => {
+                        for (_type, value) in types.iter().zip(values) {
+                            self.check_value_type(_type, value)?;
+                        }
+                    }
+                    (VariantFields::Struct { fields: types }, VariantFields::Struct { fields: values }) => {
+                        for (field, value) in values {
+                            if let Some((_, _type)) = types.iter().find(|(name, _)| name.lexeme == field.lexeme) {
+                                self.check_value_type(_type, value)?;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                Ok(())
+            }
+            Expression::Match { arms, .. } => {
+                for arm in arms {
+                    self.check_pattern(&arm.pattern)?;
+                }
+                // Patterns should have the same shape before looking for missing values
+                check_arms(arms, &self.declarations.enums)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn find_variant(&self, enum_name: &str, variant: &str) -> Option<&Variant> {
+        self.declarations.enums.get(enum_name)?.iter().find(|declared| declared.name.lexeme == variant)
+    }
+
+    /// Checks that values in variant patterns can be stored in their fields
+    fn check_pattern(&self, pattern: &Pattern) -> Result<(), String> {
+        match pattern {
+            Pattern::Tuple { elements } => {
+                for element in elements {
+                    self.check_pattern(element)?;
+                }
+                Ok(())
+            }
+            Pattern::Variant { enum_name, variant, fields } => {
+                let Some(declared) = self.find_variant(&enum_name.lexeme, &variant.lexeme) else {
+                    return Ok(());
+                };
+                let mut checked = vec![];
+                match (&declared.fields, fields) {
+                    (VariantFields::Tuple { values: types }, VariantFields::Tuple { values }) => {
+                        checked.extend(types.iter().zip(values));
+                    }
+                    (VariantFields::Struct { fields: types }, VariantFields::Struct { fields: values }) => {
+                        for (field, value) in values {
+                            if let Some((_, _type)) = types.iter().find(|(name, _)| name.lexeme == field.lexeme) {
+                                checked.push((_type, value));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                for (_type, value) in checked {
+                    let expected = self.type_of(_type);
+                    let found = value.get_type()?;
+                    if !expected.agrees_with(found) {
+                        return Err(format!("Pattern `{}` should match `{}`, found `{}`", value, expected, found));
+                    }
+                    self.check_pattern(value)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns checker type of annotation, `Unknown` if it can't be checked
+    fn type_of(&self, _type: &TypeExpr) -> Type {
+        match Ty::from_type(_type, &vec![]) {
+            Ty::Named(name) if self.declarations.enums.contains_key(&name) => Type::Enum,
+            Ty::Named(name) if self.declarations.classes.contains_key(&name) || self.declarations.interfaces.contains(&name) => Type::Object,
+            _type => _type.to_checked(),
+        }
+    }
+
+    /// Checks that `value` can be stored in place with `_type` annotation
+    fn check_value_type(&self, _type: &TypeExpr, value: &Expression) -> Result<(), String> {
+        let expected = self.type_of(_type);
+        let found = value.check_and_get_type()?;
+        if !expected.agrees_with(found) {
+            return Err(format!("Expected `{}`, found `{}`", Ty::from_type(_type, &vec![]).name(), found));
+        }
+        if let (TypeExpr::Named { name }, Expression::EnumVariant { enum_name, .. }) = (_type, value) {
+            if name.lexeme != enum_name.lexeme {
+                return Err(format!("Expected `{}`, found `{}`", name.lexeme, enum_name.lexeme));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Pass for TypeCheck {
+    fn name(&self) -> &'static str {
+        "type_check"
+    }
+
+    fn run(&mut self, program: &mut Program) -> Result<(), String> {
+        for item in program.items.iter_mut() {
+            walk_all(item.statement_mut(), &mut |statement| {
+                self.declarations.declare_type(statement, false);
+                Ok(())
+            }, &mut |_| Ok(()))?;
+            // Top level functions can be called before they are declared
+            if let Statement::Function { name, generics, arguments, _type, .. } = item.statement() {
+                self.declarations.functions.insert(name.lexeme.clone(), signature(generics, arguments, _type));
+            }
+        }
+        let checker = &*self;
+        let mut inference = Inference::new(&checker.declarations);
+        check_items(program, |item| {
+            inference.check_item(item.statement())?;
+            walk_statement(item.statement_mut(), &mut |expression| expression.check_and_get_type().map(|_| ()))?;
+            walk_all(
+                item.statement_mut(),
+                &mut |statement| checker.check_statement(statement),
+                &mut |expression| checker.check_expression(expression)
+            )
+        })
+    }
+}
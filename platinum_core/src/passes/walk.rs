@@ -0,0 +1,304 @@
+use crate::parser::{expr::Expression, stmt::{Argument, Statement, UseArgument, VariantFields}, types::TypeExpr};
+
+pub type Visit<'a> = dyn FnMut(&mut Expression) -> Result<(), String> + 'a;
+pub type VisitStatement<'a> = dyn FnMut(&mut Statement) -> Result<(), String> + 'a;
+
+/// Calls `visit` for every expression which is parsed on its own: values of `let`,
+/// conditions, returned values, expression statements, array lengths in types and so on.
+/// Statements inside of expressions (bodies of lambdas, blocks, loops and match arms) are
+/// walked before the expression which contains them.
+pub fn walk_statement(statement: &mut Statement, visit: &mut Visit) -> Result<(), String> {
+    Walker { roots: visit, every: &mut |_| Ok(()), statements: &mut |_| Ok(()), lengths: &mut |_| Ok(()) }.statement(statement)
+}
+
+pub fn walk_statements(statements: &mut Vec<Statement>, visit: &mut Visit) -> Result<(), String> {
+    Walker { roots: visit, every: &mut |_| Ok(()), statements: &mut |_| Ok(()), lengths: &mut |_| Ok(()) }.statements(statements)
+}
+
+/// Calls `visit_statement` for every statement and `visit` for every expression, including ones
+/// inside of other expressions. Everything inside of statement or expression is visited before it
+pub fn walk_all(statement: &mut Statement, visit_statement: &mut VisitStatement, visit: &mut Visit) -> Result<(), String> {
+    Walker { roots: &mut |_| Ok(()), every: visit, statements: visit_statement, lengths: &mut |_| Ok(()) }.statement(statement)
+}
+
+/// Calls `visit` for every length of array, in types `[u8; 4]` and in values `[0; 4]`,
+/// before anything inside of the length
+pub fn walk_lengths(statement: &mut Statement, visit: &mut Visit) -> Result<(), String> {
+    Walker { roots: &mut |_| Ok(()), every: &mut |_| Ok(()), statements: &mut |_| Ok(()), lengths: visit }.statement(statement)
+}
+
+struct Walker<'v, 'a> {
+    roots: &'v mut Visit<'a>,
+    every: &'v mut Visit<'a>,
+    statements: &'v mut VisitStatement<'a>,
+    lengths: &'v mut Visit<'a>,
+}
+
+impl Walker<'_, '_> {
+    fn statement(&mut self, statement: &mut Statement) -> Result<(), String> {
+        match statement {
+            Statement::Block { statements } => self.statements(statements)?,
+            Statement::Assigment { expression } => self.expression(expression)?,
+            Statement::Let { _type, value, .. } => {
+                if let Some(_type) = _type {
+                    self.type_expr(_type)?;
+                }
+                if let Some(value) = value {
+                    self.expression(value)?;
+                }
+            }
+            Statement::Const { _type, value, .. } |
+            Statement::Static { _type, value, .. } => {
+                self.type_expr(_type)?;
+                self.expression(value)?;
+            }
+            Statement::Function { _type, arguments, block, .. } => {
+                self.arguments(arguments)?;
+                self.type_expr(_type)?;
+                self.statement(block)?;
+            }
+            Statement::FunctionUse { arguments, .. } => self.use_arguments(arguments)?,
+            Statement::IfElse { condition, if_block, else_block } => {
+                self.expression(condition)?;
+                self.statement(if_block)?;
+                if let Some(else_block) = else_block {
+                    self.statement(else_block)?;
+                }
+            }
+            Statement::Loop { block, .. } => self.statement(block)?,
+            Statement::For { container, block, .. } => {
+                self.statement(container)?;
+                self.statement(block)?;
+            }
+            Statement::While { condition, block, .. } |
+            Statement::DoWhile { block, condition, .. } => {
+                self.expression(condition)?;
+                self.statement(block)?;
+            }
+            Statement::Break { value, .. } => {
+                if let Some(value) = value {
+                    self.expression(value)?;
+                }
+            }
+            Statement::Return { returned } => {
+                if let Some(returned) = returned {
+                    self.expression(returned)?;
+                }
+            }
+            Statement::Enum { variants, .. } => {
+                for variant in variants {
+                    match &mut variant.fields {
+                        VariantFields::Unit => {}
+                        VariantFields::Tuple { values } => {
+                            for value in values {
+                                self.type_expr(value)?;
+                            }
+                        }
+                        VariantFields::Struct { fields } => {
+                            for (_, _type) in fields {
+                                self.type_expr(_type)?;
+                            }
+                        }
+                    }
+                }
+            }
+            Statement::Interface { methods, .. } => {
+                for method in methods {
+                    self.arguments(&mut method.arguments)?;
+                    self.type_expr(&mut method._type)?;
+                    if let Some(block) = &mut method.block {
+                        self.statement(block)?;
+                    }
+                }
+            }
+            Statement::Class { fields, methods, .. } => {
+                self.statements(fields)?;
+                self.statements(methods)?;
+            }
+            Statement::Continue { .. } |
+            Statement::Module { .. } |
+            Statement::Import { .. } => {}
+        }
+        (self.statements)(statement)
+    }
+
+    fn statements(&mut self, statements: &mut Vec<Statement>) -> Result<(), String> {
+        for statement in statements {
+            self.statement(statement)?;
+        }
+        Ok(())
+    }
+
+    fn expression(&mut self, expression: &mut Expression) -> Result<(), String> {
+        self.nested(expression)?;
+        (self.roots)(expression)
+    }
+
+    fn arguments(&mut self, arguments: &mut Vec<Argument>) -> Result<(), String> {
+        for argument in arguments {
+            match argument {
+                Argument::NotOptional { _type, .. } => self.type_expr(_type)?,
+                Argument::Optional { _type, value, .. } => {
+                    self.type_expr(_type)?;
+                    self.expression(value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn use_arguments(&mut self, arguments: &mut Vec<UseArgument>) -> Result<(), String> {
+        for argument in arguments {
+            match argument {
+                UseArgument::Expr { value } |
+                UseArgument::Optional { value, .. } => self.expression(value)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Lengths of array types are expressions on their own
+    fn type_expr(&mut self, _type: &mut TypeExpr) -> Result<(), String> {
+        match _type {
+            TypeExpr::Named { .. } => {}
+            TypeExpr::Generic { arguments: types, .. } |
+            TypeExpr::Tuple { elements: types } => {
+                for _type in types {
+                    self.type_expr(_type)?;
+                }
+            }
+            TypeExpr::Nullable { inner } => self.type_expr(inner)?,
+            TypeExpr::Array { element, size } => {
+                self.type_expr(element)?;
+                if let Some(size) = size {
+                    (self.lengths)(size)?;
+                    self.expression(size)?;
+                }
+            }
+            TypeExpr::Map { key, value } => {
+                self.type_expr(key)?;
+                self.type_expr(value)?;
+            }
+            TypeExpr::Function { arguments, returned } => {
+                for argument in arguments {
+                    self.type_expr(argument)?;
+                }
+                self.type_expr(returned)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Walks statements and values of blocks inside of `expression`, then visits `expression` with `every`
+    fn nested(&mut self, expression: &mut Expression) -> Result<(), String> {
+        match expression {
+            Expression::Binary { left, right, .. } |
+            Expression::EqualtyComparison { left, right, .. } |
+            Expression::Logical { left, right, .. } |
+            Expression::NullCoalescing { left, right, .. } => {
+                self.nested(left)?;
+                self.nested(right)?;
+            }
+            Expression::Ternary { result, true_expression, false_expression } => {
+                self.nested(result)?;
+                self.nested(true_expression)?;
+                self.nested(false_expression)?;
+            }
+            Expression::Unary { right: inner, .. } |
+            Expression::Grouping { expression: inner } |
+            Expression::Increment { target: inner, .. } |
+            Expression::Get { object: inner, .. } |
+            Expression::TupleIndex { object: inner, .. } |
+            Expression::Loop { body: inner, .. } => self.nested(inner)?,
+            Expression::Assign { target, value, .. } => {
+                self.nested(target)?;
+                self.nested(value)?;
+            }
+            Expression::Call { callee, type_arguments, arguments } => {
+                self.nested(callee)?;
+                for _type in type_arguments {
+                    self.type_expr(_type)?;
+                }
+                for argument in arguments {
+                    match argument {
+                        UseArgument::Expr { value } |
+                        UseArgument::Optional { value, .. } => self.nested(value)?,
+                    }
+                }
+            }
+            Expression::Array { elements } |
+            Expression::Tuple { elements } => {
+                for element in elements {
+                    self.nested(element)?;
+                }
+            }
+            Expression::ArrayRepeat { value, count } => {
+                self.nested(value)?;
+                (self.lengths)(count)?;
+                self.nested(count)?;
+            }
+            Expression::Index { object, index } => {
+                self.nested(object)?;
+                self.nested(index)?;
+            }
+            Expression::Map { entries } => {
+                for (key, value) in entries {
+                    self.nested(key)?;
+                    self.nested(value)?;
+                }
+            }
+            Expression::Range { start, end, .. } => {
+                for bound in [start, end].into_iter().flatten() {
+                    self.nested(bound)?;
+                }
+            }
+            Expression::Lambda { arguments, _type, body, .. } => {
+                self.arguments(arguments)?;
+                if let Some(_type) = _type {
+                    self.type_expr(_type)?;
+                }
+                self.statement(body)?;
+            }
+            Expression::Match { value, arms } => {
+                self.nested(value)?;
+                for arm in arms {
+                    if let Some(guard) = &mut arm.guard {
+                        self.nested(guard)?;
+                    }
+                    self.statement(&mut arm.body)?;
+                }
+            }
+            Expression::Block { statements, value } => {
+                self.statements(statements)?;
+                if let Some(value) = value {
+                    self.expression(value)?;
+                }
+            }
+            Expression::If { condition, then_branch, else_branch } => {
+                self.nested(condition)?;
+                self.nested(then_branch)?;
+                if let Some(else_branch) = else_branch {
+                    self.nested(else_branch)?;
+                }
+            }
+            Expression::EnumVariant { fields, .. } => {
+                match fields {
+                    VariantFields::Unit => {}
+                    VariantFields::Tuple { values } => {
+                        for value in values {
+                            self.nested(value)?;
+                        }
+                    }
+                    VariantFields::Struct { fields } => {
+                        for (_, value) in fields {
+                            self.nested(value)?;
+                        }
+                    }
+                }
+            }
+            Expression::Variable { .. } |
+            Expression::Literal { .. } => {}
+        }
+        (self.every)(expression)
+    }
+}
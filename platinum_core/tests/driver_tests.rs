@@ -0,0 +1,91 @@
+use platinum_core::driver::session::Driver;
+
+#[test]
+fn parse_program_with_imports() {
+    let root = "./tests/driver_codes/program";
+
+    let mut driver = Driver::new(root);
+    let files = driver.parse_program("./tests/driver_codes/program/main.ppl").unwrap();
+
+    let modules: Vec<&str> = files.iter().map(|file| file.module.as_str()).collect();
+    assert_eq!(modules, vec!["math.vector", "geometry", "main"]);
+    assert_eq!(files[1].file_path, "./tests/driver_codes/program/geometry.ppl");
+    assert_eq!(files[2].statements.len(), 7);
+    assert_eq!("(import { Shape, area, Counter } from geometry)", files[2].statements[2].to_string());
+}
+
+#[test]
+fn parse_program_imported_function_type_error() {
+    let root = "./tests/driver_codes/program";
+
+    let mut driver = Driver::new(root);
+    let result = driver.parse_program("./tests/driver_codes/program/wrong_type.ppl");
+
+    assert_eq!(result.unwrap_err(), "./tests/driver_codes/program/wrong_type.ppl: Expected `String`, found `i32`\n");
+}
+
+#[test]
+fn parse_program_missing_module_error() {
+    let root = "./tests/driver_codes/program";
+
+    let mut driver = Driver::new(root);
+    let result = driver.parse_program("./tests/driver_codes/program/missing_module.ppl");
+
+    assert_eq!(
+        result.unwrap_err(),
+        "./tests/driver_codes/program/missing_module.ppl: Module `math.matrix` not found, expected file `./tests/driver_codes/program/math/matrix.ppl`"
+    );
+}
+
+#[test]
+fn parse_program_missing_name_error() {
+    let root = "./tests/driver_codes/program";
+
+    let mut driver = Driver::new(root);
+    let result = driver.parse_program("./tests/driver_codes/program/missing_name.ppl");
+
+    assert_eq!(result.unwrap_err(), "./tests/driver_codes/program/missing_name.ppl: Module `math.vector` has no declaration `sub`");
+}
+
+#[test]
+fn parse_program_wrong_module_name_error() {
+    let root = "./tests/driver_codes/program";
+
+    let mut driver = Driver::new(root);
+    let result = driver.parse_program("./tests/driver_codes/program/wrong_module.ppl");
+
+    assert_eq!(result.unwrap_err(), "./tests/driver_codes/program/wrong_module.ppl: File declares module `other`, expected `wrong_module`");
+}
+
+#[test]
+fn parse_program_import_cycle_error() {
+    let root = "./tests/driver_codes/cycle";
+
+    let mut driver = Driver::new(root);
+    let result = driver.parse_program("./tests/driver_codes/cycle/a.ppl");
+
+    assert_eq!(result.unwrap_err(), "./tests/driver_codes/cycle/b.ppl: Import cycle `a -> b -> a`");
+}
+
+#[test]
+fn parse_program_not_pub_import_error() {
+    let root = "./tests/driver_codes/program";
+
+    let mut driver = Driver::new(root);
+    let result = driver.parse_program("./tests/driver_codes/program/private_name.ppl");
+
+    assert_eq!(result.unwrap_err(), "./tests/driver_codes/program/private_name.ppl: `helper` isn't `pub` in module `geometry`");
+}
+
+#[test]
+fn parse_program_not_pub_member_error() {
+    let root = "./tests/driver_codes/program";
+
+    let mut driver = Driver::new(root);
+    let result = driver.parse_program("./tests/driver_codes/program/hidden_member.ppl");
+
+    assert_eq!(
+        result.unwrap_err(),
+        "./tests/driver_codes/program/hidden_member.ppl: `hidden` of class `Counter` isn't `pub` and can't be used outside of its module\n"
+    );
+}
@@ -88,7 +88,7 @@ fn handle_multi_char_tokens() {
 
 #[test]
 fn handle_comments_tokens() {
-    let file_path = "tests\\lexer_codes\\handle_comments_tokens.ppl";
+    let file_path = "tests/lexer_codes/handle_comments_tokens.ppl";
     let src: String = read_file(file_path).unwrap();
 
     let mut scanner = Scanner::new(file_path, src.as_str());
@@ -165,7 +165,7 @@ fn handle_char_tokens() {
 
 #[test]
 fn handle_special_chars_tokens() {
-    let file_path = "tests\\lexer_codes\\handle_special_chars_tokens.ppl";
+    let file_path = "tests/lexer_codes/handle_special_chars_tokens.ppl";
     let src: String = read_file(file_path).unwrap();
 
     let mut scanner = Scanner::new(file_path, src.as_str());
@@ -245,7 +245,7 @@ fn handle_number_with_underscore_token() {
 
 #[test]
 fn handle_numbers_tokens() {
-    let file_path = "tests\\lexer_codes\\handle_number_tokens.ppl";
+    let file_path = "tests/lexer_codes/handle_number_tokens.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -342,7 +342,7 @@ fn handle_not_ascii_idetifier_token() {
 
 #[test]
 fn handle_standart_idetifiers_token() {
-    let file_path = "tests\\lexer_codes\\handle_standart_idetifiers_token.ppl";
+    let file_path = "tests/lexer_codes/handle_standart_idetifiers_token.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -396,4 +396,178 @@ fn handle_standart_idetifiers_token() {
 
 // for token in scanner.tokens.iter() {
 //     println!("{:?}", token);
-// }
\ No newline at end of file
+// }
+#[test]
+fn handle_bitwise_assigment_tokens() {
+    let file_path = "<stdin>";
+    let src = "&= |= ^= & | ^";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 7);
+    assert_eq!(scanner.tokens[0].lexeme, "&=".to_string());
+    assert_eq!(scanner.tokens[1].lexeme, "|=".to_string());
+    assert_eq!(scanner.tokens[2].lexeme, "^=".to_string());
+
+    assert_eq!(scanner.tokens[0].token_type, AmpersantEqual);
+    assert_eq!(scanner.tokens[1].token_type, BarEqual);
+    assert_eq!(scanner.tokens[2].token_type, CaretEqual);
+    assert_eq!(scanner.tokens[3].token_type, Ampersant);
+    assert_eq!(scanner.tokens[4].token_type, Bar);
+    assert_eq!(scanner.tokens[5].token_type, Caret);
+    assert_eq!(scanner.tokens[6].token_type, EOF);
+}
+
+#[test]
+fn handle_question_tokens() {
+    let file_path = "<stdin>";
+    let src = "? ?? ??= ?.";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 5);
+    assert_eq!(scanner.tokens[0].lexeme, "?".to_string());
+    assert_eq!(scanner.tokens[1].lexeme, "??".to_string());
+    assert_eq!(scanner.tokens[2].lexeme, "??=".to_string());
+    assert_eq!(scanner.tokens[3].lexeme, "?.".to_string());
+
+    assert_eq!(scanner.tokens[0].token_type, Question);
+    assert_eq!(scanner.tokens[1].token_type, QuestionQuestion);
+    assert_eq!(scanner.tokens[2].token_type, QuestionQuestionEqual);
+    assert_eq!(scanner.tokens[3].token_type, QuestionDot);
+    assert_eq!(scanner.tokens[4].token_type, EOF);
+}
+
+#[test]
+fn handle_number_before_dot_dot_tokens() {
+    let file_path = "<stdin>";
+    let src = "1..3 1.5";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 5);
+    assert_eq!(scanner.tokens[0].token_type, Int { value: "1".to_string(), num_type: NumberType::UntypedInt });
+    assert_eq!(scanner.tokens[1].token_type, DotDot);
+    assert_eq!(scanner.tokens[2].token_type, Int { value: "3".to_string(), num_type: NumberType::UntypedInt });
+    assert_eq!(scanner.tokens[3].token_type, Float { value: "1.5".to_string(), num_type: NumberType::UntypedFloat });
+    assert_eq!(scanner.tokens[4].token_type, EOF);
+}
+
+#[test]
+fn handle_range_tokens() {
+    let file_path = "<stdin>";
+    let src = "0..=10 ..";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 5);
+    assert_eq!(scanner.tokens[1].lexeme, "..=".to_string());
+    assert_eq!(scanner.tokens[3].lexeme, "..".to_string());
+
+    assert_eq!(scanner.tokens[0].token_type, Int { value: "0".to_string(), num_type: NumberType::UntypedInt });
+    assert_eq!(scanner.tokens[1].token_type, DotDotEqual);
+    assert_eq!(scanner.tokens[2].token_type, Int { value: "10".to_string(), num_type: NumberType::UntypedInt });
+    assert_eq!(scanner.tokens[3].token_type, DotDot);
+    assert_eq!(scanner.tokens[4].token_type, EOF);
+}
+
+#[test]
+fn handle_arrow_tokens() {
+    let file_path = "<stdin>";
+    let src = "=> -> ==";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 4);
+    assert_eq!(scanner.tokens[0].lexeme, "=>".to_string());
+
+    assert_eq!(scanner.tokens[0].token_type, EqualGreater);
+    assert_eq!(scanner.tokens[1].token_type, MinusGreater);
+    assert_eq!(scanner.tokens[2].token_type, EqualEqual);
+    assert_eq!(scanner.tokens[3].token_type, EOF);
+}
+
+#[test]
+fn handle_colon_colon_tokens() {
+    let file_path = "<stdin>";
+    let src = "Shape::Empty a: b";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 7);
+    assert_eq!(scanner.tokens[1].lexeme, "::".to_string());
+
+    assert_eq!(scanner.tokens[1].token_type, ColonColon);
+    assert_eq!(scanner.tokens[4].token_type, Colon);
+    assert_eq!(scanner.tokens[6].token_type, EOF);
+}
+
+#[test]
+fn handle_declaration_keywords() {
+    let file_path = "<stdin>";
+    let src = "module import from pub private const static";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 8);
+
+    assert_eq!(scanner.tokens[0].token_type, Module);
+    assert_eq!(scanner.tokens[1].token_type, Import);
+    assert_eq!(scanner.tokens[2].token_type, From);
+    assert_eq!(scanner.tokens[3].token_type, Pub);
+    assert_eq!(scanner.tokens[4].token_type, Private);
+    assert_eq!(scanner.tokens[5].token_type, Const);
+    assert_eq!(scanner.tokens[6].token_type, Static);
+}
+
+#[test]
+fn handle_attribute() {
+    let file_path = "<stdin>";
+    let src = "#[deprecated(\"use x\")]";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 8);
+
+    assert_eq!(scanner.tokens[0].token_type, Hash);
+    assert_eq!(scanner.tokens[1].token_type, LeftBrace);
+    assert_eq!(scanner.tokens[2].token_type, Identifier { value: "deprecated".to_string() });
+    assert_eq!(scanner.tokens[3].token_type, LeftParen);
+    assert_eq!(scanner.tokens[4].token_type, StringT { value: "use x".to_string() });
+    assert_eq!(scanner.tokens[5].token_type, RightParen);
+    assert_eq!(scanner.tokens[6].token_type, RightBrace);
+}
+
+#[test]
+fn handle_label() {
+    let file_path = "<stdin>";
+    let src = "'outer: 'a' 'b";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 5);
+
+    assert_eq!(scanner.tokens[0].token_type, Label { value: "outer".to_string() });
+    assert_eq!(scanner.tokens[0].lexeme, "'outer");
+    assert_eq!(scanner.tokens[1].token_type, Colon);
+    assert_eq!(scanner.tokens[2].token_type, Char { value: 'a' });
+    assert_eq!(scanner.tokens[3].token_type, Label { value: "b".to_string() });
+}
+
+#[test]
+fn handle_logical_keywords() {
+    let file_path = "<stdin>";
+    let src = "not ! and && or ||";
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    assert_eq!(scanner.tokens.len(), 7);
+
+    assert_eq!(scanner.tokens[0].token_type, Bang);
+    assert_eq!(scanner.tokens[0].lexeme, "not");
+    assert_eq!(scanner.tokens[1].token_type, Bang);
+    assert_eq!(scanner.tokens[2].token_type, And);
+    assert_eq!(scanner.tokens[3].token_type, And);
+    assert_eq!(scanner.tokens[4].token_type, Or);
+    assert_eq!(scanner.tokens[5].token_type, Or);
+}
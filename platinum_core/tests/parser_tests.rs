@@ -1,7 +1,10 @@
 use std::{self, fs};
 
 use platinum_core::lexer::lexer::Scanner;
+use platinum_core::lexer::token::TokenType;
+use platinum_core::parser::operators::{self, precedence_table, TERM, UNARY};
 use platinum_core::parser::parser::Parser;
+use platinum_core::parser::stmt::Program;
 
 fn read_file(path: &str) -> Result<String, Box<dyn std::error::Error>> {
     let src: String = fs::read_to_string(path)?.parse()?;
@@ -30,7 +33,7 @@ fn parse_expr() {
 
 #[test]
 fn parse_block_stmt() {
-    let file_path = "tests\\parser_codes\\parse_block_stmt.ppl";
+    let file_path = "tests/parser_codes/parse_block_stmt.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -65,7 +68,7 @@ fn parse_let_stmt() {
         Err(msg) => panic!("{}", msg)
     }
 
-    assert_eq!("(let mut Identifier { value: \"SASA\" } SASA: Identifier { value: \"u8\" } u8 = (assigment 10))", statements[0].to_string());
+    assert_eq!("(let mut Identifier { value: \"SASA\" } SASA: Identifier { value: \"u8\" } u8 = 10)", statements[0].to_string());
 }
 
 #[test]
@@ -85,12 +88,12 @@ fn parse_let_stmt2() {
         Err(msg) => panic!("{}", msg)
     }
 
-    assert_eq!("(let mut Identifier { value: \"SASA\" } SASA: Identifier { value: \"u8\" } u8 = (assigment null))", statements[0].to_string());
+    assert_eq!("(let mut Identifier { value: \"SASA\" } SASA: Identifier { value: \"u8\" } u8)", statements[0].to_string());
 }
 
 #[test]
 fn parse_if_stmt() {
-    let file_path = "tests\\parser_codes\\parse_if_stmt.ppl";
+    let file_path = "tests/parser_codes/parse_if_stmt.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -104,12 +107,12 @@ fn parse_if_stmt() {
         Err(msg) => panic!("{}", msg)
     }
 
-    assert_eq!("(if ((assigment true)) (block \n(assigment true)\n))", statements[0].to_string());
+    assert_eq!("(if (true) (block \n(assigment true)\n))", statements[0].to_string());
 }
 
 #[test]
 fn parse_if_else_stmt() {
-    let file_path = "tests\\parser_codes\\parse_if_else_stmt.ppl";
+    let file_path = "tests/parser_codes/parse_if_else_stmt.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -123,12 +126,12 @@ fn parse_if_else_stmt() {
         Err(msg) => panic!("{}", msg)
     }
 
-    assert_eq!("(if ((assigment true)) (block \n(assigment true)\n) else (block \n(assigment \"hello\")\n))", statements[0].to_string());
+    assert_eq!("(if (true) (block \n(assigment true)\n) else (block \n(assigment \"hello\")\n))", statements[0].to_string());
 }
 
 #[test]
 fn parse_if_else_stmt2() {
-    let file_path = "tests\\parser_codes\\parse_if_else_stmt2.ppl";
+    let file_path = "tests/parser_codes/parse_if_else_stmt2.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -142,12 +145,12 @@ fn parse_if_else_stmt2() {
         Err(msg) => panic!("{}", msg)
     }
 
-    assert_eq!("(if ((assigment true)) (block \n(assigment true)\n) else (if ((assigment false)) (block \n(assigment 123)\n) else (block \n(assigment \"hello\")\n)))", statements[0].to_string());
+    assert_eq!("(if (true) (block \n(assigment true)\n) else (if (false) (block \n(assigment 123)\n) else (block \n(assigment \"hello\")\n)))", statements[0].to_string());
 }
 
 #[test]
 fn parse_func_define_stmt() {
-    let file_path = "tests\\parser_codes\\parse_func_define_stmt.ppl";
+    let file_path = "tests/parser_codes/parse_func_define_stmt.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -161,12 +164,12 @@ fn parse_func_define_stmt() {
         Err(msg) => panic!("{}", msg)
     }
 
-    assert_eq!("(fun Identifier { value: \"hello\" } hello([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (1, 10, 11) }, _type: Token { token_type: Identifier { value: \"u8\" }, lexeme: \"u8\", possition: (1, 13, 15) } }, Optional { name: Token { token_type: Identifier { value: \"b\" }, lexeme: \"b\", possition: (1, 17, 18) }, _type: Token { token_type: Identifier { value: \"i128\" }, lexeme: \"i128\", possition: (1, 20, 24) }, value: Literal { value: Token { token_type: Int { value: \"2583\", num_type: UntypedInt }, lexeme: \"2583\", possition: (1, 31, 32) } } }]) -> Null void (block \n(assigment 2)\n))", statements[0].to_string());
+    assert_eq!("(fun Identifier { value: \"hello\" } hello([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (1, 10, 11) }, _type: Named { name: Token { token_type: Identifier { value: \"u8\" }, lexeme: \"u8\", possition: (1, 13, 15) } } }, Optional { name: Token { token_type: Identifier { value: \"b\" }, lexeme: \"b\", possition: (1, 17, 18) }, _type: Named { name: Token { token_type: Identifier { value: \"i128\" }, lexeme: \"i128\", possition: (1, 20, 24) } }, value: Literal { value: Token { token_type: Int { value: \"2583\", num_type: UntypedInt }, lexeme: \"2583\", possition: (1, 31, 32) } } }]) -> Null void (block \n(assigment 2)\n))", statements[0].to_string());
 }
 
 #[test]
 fn parse_func_use_stmt() {
-    let file_path = "tests\\parser_codes\\parse_func_use_stmt.ppl";
+    let file_path = "tests/parser_codes/parse_func_use_stmt.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -185,7 +188,7 @@ fn parse_func_use_stmt() {
 
 #[test]
 fn parse_loop_stmt() {
-    let file_path = "tests\\parser_codes\\parse_loop_stmt.ppl";
+    let file_path = "tests/parser_codes/parse_loop_stmt.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -199,12 +202,12 @@ fn parse_loop_stmt() {
         Err(msg) => panic!("{}", msg)
     }
     
-    assert_eq!("(loop (block \n(let Identifier { value: \"a\" } a: Identifier { value: \"u8\" } u8 = (assigment 123))\n))", statements[0].to_string());
+    assert_eq!("(loop (block \n(let Identifier { value: \"a\" } a: Identifier { value: \"u8\" } u8 = 123)\n))", statements[0].to_string());
 }
 
 #[test]
 fn parse_for_stmt() {
-    let file_path = "tests\\parser_codes\\parse_for_stmt.ppl";
+    let file_path = "tests/parser_codes/parse_for_stmt.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -218,12 +221,12 @@ fn parse_for_stmt() {
         Err(msg) => panic!("{}", msg)
     }
     
-    assert_eq!("(for (Identifier { value: \"i\" } i in (range (assigment 0)..(assigment 11))) (block \n(assigment 2)\n))", statements[0].to_string());
+    assert_eq!("(for (Identifier { value: \"i\" } i in (assigment (range 0..11))) (block \n(assigment 2)\n))", statements[0].to_string());
 }
 
 #[test]
 fn parse_while_stmt() {
-    let file_path = "tests\\parser_codes\\parse_while_stmt.ppl";
+    let file_path = "tests/parser_codes/parse_while_stmt.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -237,12 +240,12 @@ fn parse_while_stmt() {
         Err(msg) => panic!("{}", msg)
     }
     
-    assert_eq!("(while (assigment false) (block \n(let Identifier { value: \"a\" } a: Identifier { value: \"u8\" } u8 = (assigment null))\n))", statements[0].to_string());
+    assert_eq!("(while false (block \n(let Identifier { value: \"a\" } a: Identifier { value: \"u8\" } u8)\n))", statements[0].to_string());
 }
 
 #[test]
 fn parse_do_while_stmt() {
-    let file_path = "tests\\parser_codes\\parse_do_while_stmt.ppl";
+    let file_path = "tests/parser_codes/parse_do_while_stmt.ppl";
     let src = read_file(file_path).unwrap();
     let mut scanner = Scanner::new(file_path, src.as_str());
     let _ = scanner.scan_tokens();
@@ -256,7 +259,7 @@ fn parse_do_while_stmt() {
         Err(msg) => panic!("{}", msg)
     }
     
-    assert_eq!("(do (block \n(let Identifier { value: \"a\" } a: Identifier { value: \"u8\" } u8 = (assigment null))\n) while (assigment false))", statements[0].to_string());
+    assert_eq!("(do (block \n(let Identifier { value: \"a\" } a: Identifier { value: \"u8\" } u8)\n) while false)", statements[0].to_string());
 }
 
 #[test]
@@ -615,6 +618,18 @@ fn test_nested_ternary_expression() {
     assert_eq!("(assigment 2)", statements[0].to_string());
 }
 
+#[test]
+fn test_ternary_without_colon_error() {
+    let file_path = "<stdin>";
+    let src = "true ? 2 3";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: `:` expected after left result\n", parser.parse().unwrap_err());
+}
+
 #[test]
 fn test_unary_not_expression() {
     let file_path = "<stdin>";
@@ -784,6 +799,2780 @@ fn test_complex_expression_10() {
 }
 
 
+#[test]
+fn test_assigment_expression() {
+    let file_path = "<stdin>";
+    let src = "a = 5 * 2";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (= a 10))", statements[0].to_string());
+}
+
+#[test]
+fn test_compound_assigment_expression() {
+    let file_path = "<stdin>";
+    let src = "a = b <<= c += 2";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (= a (<<= b (+= c 2))))", statements[0].to_string());
+}
+
+#[test]
+fn test_field_assigment_expression() {
+    let file_path = "<stdin>";
+    let src = "this.bread -= 1";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (-= (get this bread) 1))", statements[0].to_string());
+}
+
+#[test]
+fn test_increment_expression() {
+    let file_path = "<stdin>";
+    let src = "{\n    i++;\n    --j;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(block \n(assigment (i ++))\n(assigment (-- j))\n)", statements[0].to_string());
+}
+
+#[test]
+fn test_invalid_assigment_target() {
+    let file_path = "<stdin>";
+    let src = "a + 1 = 5";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Invalid assigment target `(+ a 1)` at possition (1, 6, 7)\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_compound_assigment_type_error() {
+    let file_path = "<stdin>";
+    let src = "let a = 1;\na += true;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Can't use operator `+=` with `i32` and `bool`\n".to_string()), parser.parse());
+}
+
+
+#[test]
+fn test_null_coalescing_expression() {
+    let file_path = "<stdin>";
+    let src = "a ?? b ?? 10";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (?? a (?? b 10)))", statements[0].to_string());
+}
+
+#[test]
+fn test_null_coalescing_folding() {
+    let file_path = "<stdin>";
+    let src = "null ?? 5 + 2";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment 7)", statements[0].to_string());
+}
+
+#[test]
+fn test_null_coalescing_with_ternary() {
+    let file_path = "<stdin>";
+    let src = "a ?? true ? 1 : 2";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (ternary (?? a true) ? 1 : 2))", statements[0].to_string());
+}
+
+#[test]
+fn test_null_coalescing_type_error() {
+    let file_path = "<stdin>";
+    let src = "5 ?? \"five\"";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Can't use operator `??` with `int` and `String` types\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_null_coalescing_assigment() {
+    let file_path = "<stdin>";
+    let src = "a ??= 1";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (??= a 1))", statements[0].to_string());
+}
+
+#[test]
+fn test_optional_chaining_expression() {
+    let file_path = "<stdin>";
+    let src = "obj?.field.method(1 + 2, b = 3)";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (call (get (get? obj field) method) 3 b = 3))", statements[0].to_string());
+}
+
+#[test]
+fn test_optional_chaining_null_folding() {
+    let file_path = "<stdin>";
+    let src = "null?.method()";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment null)", statements[0].to_string());
+}
+
+#[test]
+fn test_array_literal_expression() {
+    let file_path = "<stdin>";
+    let src = "[1 + 1, 2, a]";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (array 2 2 a))", statements[0].to_string());
+}
+
+#[test]
+fn test_array_repeat_expression() {
+    let file_path = "<stdin>";
+    let src = "[0u8; 4 * 4]";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (array 0u8; 16))", statements[0].to_string());
+}
+
+#[test]
+fn test_index_expression() {
+    let file_path = "<stdin>";
+    let src = "a[i + 1][0] = [10, 20, 30][2]";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (= (index (index a (+ i 1)) 0) 30))", statements[0].to_string());
+}
+
+#[test]
+fn test_index_string_literal() {
+    let file_path = "<stdin>";
+    let src = "let c = \"abc\"[1];";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"c\" } c = (index \"abc\" 1))", statements[0].to_string());
+}
+
+#[test]
+fn test_index_grouping() {
+    let file_path = "<stdin>";
+    let src = "let a = [1, 2];\nlet b = (a)[0];";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"b\" } b = (index a 0))", statements[1].to_string());
+}
+
+#[test]
+fn test_array_after_match_is_not_index() {
+    let file_path = "<stdin>";
+    let src = "match 1 { _ => 2 }\n[3, 4];";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (array 3 4))", statements[1].to_string());
+}
+
+#[test]
+fn test_slice_expression() {
+    let file_path = "<stdin>";
+    let src = "a[1..3] + a[..n] + s[1..] + s[..]";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (+ (+ (+ (index a (range 1..3)) (index a (range ..n))) (index s (range 1..))) (index s (range ..))))", statements[0].to_string());
+}
+
+#[test]
+fn test_array_elements_type_error() {
+    let file_path = "<stdin>";
+    let src = "[1, \"two\", 3]";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Array elements should have the same type, found `int` and `String`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_index_out_of_bounds_error() {
+    let file_path = "<stdin>";
+    let src = "[1, 2, 3][3]";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Index out of bounds: the length is 3 but the index is 3\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_let_array_type() {
+    let file_path = "<stdin>";
+    let src = "let a: [u8; 4] = [0; 4];";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a: [Identifier { value: \"u8\" } u8; 4] = (array 0; 4))", statements[0].to_string());
+}
+
+#[test]
+fn test_let_dynamic_array_type() {
+    let file_path = "<stdin>";
+    let src = "let a: [[i32]];";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a: [[Identifier { value: \"i32\" } i32]])", statements[0].to_string());
+}
+
+#[test]
+fn test_for_array_container() {
+    let file_path = "<stdin>";
+    let src = "for (x in [1, 2, 3]) {\n    x\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(for (Identifier { value: \"x\" } x in (assigment (array 1 2 3))) (block \n(assigment x)\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_map_literal_expression() {
+    let file_path = "<stdin>";
+    let src = "#{ \"a\": 1 + 1, \"b\": x }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (map \"a\": 2, \"b\": x))", statements[0].to_string());
+}
+
+#[test]
+fn test_map_index_expression() {
+    let file_path = "<stdin>";
+    let src = "m[\"a\"] = #{}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (= (index m \"a\") (map )))", statements[0].to_string());
+}
+
+#[test]
+fn test_map_constant_lookup() {
+    let file_path = "<stdin>";
+    let src = "#{ \"a\": 1, \"b\": 2 }[\"b\"] * 10";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment 20)", statements[0].to_string());
+}
+
+#[test]
+fn test_map_keys_type_error() {
+    let file_path = "<stdin>";
+    let src = "#{ \"a\": 1, 2: 2 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Map keys should have the same type, found `String` and `int`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_map_values_type_error() {
+    let file_path = "<stdin>";
+    let src = "#{ \"a\": 1, \"b\": true }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Map values should have the same type, found `int` and `bool`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_map_duplicate_key_error() {
+    let file_path = "<stdin>";
+    let src = "#{ \"a\": 1, \"a\": 2 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Duplicate key `\"a\"` in map\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_map_missing_key_error() {
+    let file_path = "<stdin>";
+    let src = "#{ \"a\": 1 }[\"b\"]";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Key `\"b\"` isn't found in map\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_let_map_type() {
+    let file_path = "<stdin>";
+    let src = "let m: #{String: [i32]};";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"m\" } m: #{Identifier { value: \"String\" } String: [Identifier { value: \"i32\" } i32]})", statements[0].to_string());
+}
+
+#[test]
+fn test_tuple_expression() {
+    let file_path = "<stdin>";
+    let src = "(a, b + 1, (c,), (d), ())";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (tuple a (+ b 1) (tuple c) d (tuple )))", statements[0].to_string());
+}
+
+#[test]
+fn test_tuple_index_expression() {
+    let file_path = "<stdin>";
+    let src = "t.0.1 = (1, (2, 3)).1.0";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (= (get (get t 0) 1) 2))", statements[0].to_string());
+}
+
+#[test]
+fn test_tuple_index_out_of_range_error() {
+    let file_path = "<stdin>";
+    let src = "(1, 2).2";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Tuple with 2 elements has no field `2`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_grouped_tuple_index_out_of_range_error() {
+    let file_path = "<stdin>";
+    let src = "let t = ((1, 2)).5;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Tuple with 2 elements has no field `5`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_let_tuple_destructuring() {
+    let file_path = "<stdin>";
+    let src = "let (q, r) = (a / b, a % b);";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let (Identifier { value: \"q\" } q, Identifier { value: \"r\" } r) = (tuple (/ a b) (% a b)))", statements[0].to_string());
+}
+
+#[test]
+fn test_let_tuple_type() {
+    let file_path = "<stdin>";
+    let src = "let (a, (b, c)): (i32, (String, bool));";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let (Identifier { value: \"a\" } a, (Identifier { value: \"b\" } b, Identifier { value: \"c\" } c)): (Identifier { value: \"i32\" } i32, (Identifier { value: \"String\" } String, Identifier { value: \"bool\" } bool)))", statements[0].to_string());
+}
+
+#[test]
+fn test_let_tuple_arity_error() {
+    let file_path = "<stdin>";
+    let src = "let (a, b) = (1, 2, 3);";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Expected a tuple with 2 elements, found one with 3 elements\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_let_tuple_type_arity_error() {
+    let file_path = "<stdin>";
+    let src = "let (a, b): (i32,);";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Expected a tuple with 1 elements, found one with 2 elements\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_let_tuple_duplicate_binding_error() {
+    let file_path = "<stdin>";
+    let src = "let (a, a) = (1, 2);";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Identifier `a` is bound more than once in the same pattern\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_for_tuple_destructuring() {
+    let file_path = "<stdin>";
+    let src = "for ((k, v) in pairs) {\n    k\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(for ((Identifier { value: \"k\" } k, Identifier { value: \"v\" } v) in (assigment pairs)) (block \n(assigment k)\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_range_expression() {
+    let file_path = "<stdin>";
+    let src = "(0..n, 0..=2 * 5, a.., ..b, ..)";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (tuple (range 0..n) (range 0..=10) (range a..) (range ..b) (range ..)))", statements[0].to_string());
+}
+
+#[test]
+fn test_range_step_expression() {
+    let file_path = "<stdin>";
+    let src = "(0..10).step(2).rev()";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (call (get (call (get (range 0..10) step) 2) rev)))", statements[0].to_string());
+}
+
+#[test]
+fn test_range_step_type_error() {
+    let file_path = "<stdin>";
+    let src = "(0..10).step(\"two\")";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Method `step` expects `int`, found `String`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_range_negative_step_error() {
+    let file_path = "<stdin>";
+    let src = "(0..10).step(-2)";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Range step should be positive, found -2\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_range_unknown_method_error() {
+    let file_path = "<stdin>";
+    let src = "(0..10).len()";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: No method `len` on `range` type\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_range_bounds_type_error() {
+    let file_path = "<stdin>";
+    let src = "0..true";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Range bounds should be `int` or `char`, found `bool`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_for_range_expression() {
+    let file_path = "<stdin>";
+    let src = "for (i in 0..=11) {\n    i\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(for (Identifier { value: \"i\" } i in (assigment (range 0..=11))) (block \n(assigment i)\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_lambda_expression() {
+    let file_path = "<stdin>";
+    let src = "let f: fun(i32) -> i32 = fun (x: i32) -> i32 { x * 2 };";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"f\" } f: fun(Identifier { value: \"i32\" } i32) -> Identifier { value: \"i32\" } i32 = (lambda (x: Identifier { value: \"i32\" } i32) -> Identifier { value: \"i32\" } i32 (block \n(assigment (* x 2))\n)))", statements[0].to_string());
+}
+
+#[test]
+fn test_arrow_lambda_captures() {
+    let file_path = "<stdin>";
+    let src = "(a: i32, b: i32) => a < b + k";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (lambda (a: Identifier { value: \"i32\" } i32, b: Identifier { value: \"i32\" } i32) [k] (return (equalty < a (+ b k)))))", statements[0].to_string());
+}
+
+#[test]
+fn test_arrow_lambda_mutated_capture() {
+    let file_path = "<stdin>";
+    let src = "() => counter += 1";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (lambda () [mut counter] (return (+= counter 1))))", statements[0].to_string());
+}
+
+#[test]
+fn test_arrow_lambda_block_body() {
+    let file_path = "<stdin>";
+    let src = "(x: i32) -> i32 => { let y: i32 = x * 2; return y + offset; }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (lambda (x: Identifier { value: \"i32\" } i32) -> Identifier { value: \"i32\" } i32 [offset] (block \n(let Identifier { value: \"y\" } y: Identifier { value: \"i32\" } i32 = (* x 2))\n(return (+ y offset))\n)))", statements[0].to_string());
+}
+
+#[test]
+fn test_nested_lambda_captures() {
+    let file_path = "<stdin>";
+    let src = "fun () { let y: i32 = 1; (x: i32) => x + y + z }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (lambda () -> Null void [z] (block \n(let Identifier { value: \"y\" } y: Identifier { value: \"i32\" } i32 = 1)\n(assigment (lambda (x: Identifier { value: \"i32\" } i32) [y, z] (return (+ (+ x y) z))))\n)))", statements[0].to_string());
+}
+
+#[test]
+fn test_call_lambda() {
+    let file_path = "<stdin>";
+    let src = "(fun (x: i32) -> i32 { x })(1)";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (call (lambda (x: Identifier { value: \"i32\" } i32) -> Identifier { value: \"i32\" } i32 (block \n(assigment x)\n)) 1))", statements[0].to_string());
+}
+
+#[test]
+fn test_function_type_without_return() {
+    let file_path = "<stdin>";
+    let src = "let log: fun(String) = (message: String) => print(message);";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"log\" } log: fun(Identifier { value: \"String\" } String) -> Null void = (lambda (message: Identifier { value: \"String\" } String) [print] (return (call print message))))", statements[0].to_string());
+}
+
+#[test]
+fn test_lambda_argument_type_error() {
+    let file_path = "<stdin>";
+    let src = "(x) => x * 2";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `:` expected\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_arrow_lambda_body_error() {
+    let file_path = "<stdin>";
+    let src = "(x: i32) =>";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Expected expression: Token { token_type: EOF, lexeme: \"\", possition: (1, 11, 11) }\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_type() {
+    let file_path = "<stdin>";
+    let src = "let a: List<i32> = xs;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a: Identifier { value: \"List\" } List<Identifier { value: \"i32\" } i32> = xs)", statements[0].to_string());
+}
+
+#[test]
+fn test_nested_generic_type() {
+    let file_path = "<stdin>";
+    let src = "let a: Map<String, List<List<i32>>> = m;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a: Identifier { value: \"Map\" } Map<Identifier { value: \"String\" } String, Identifier { value: \"List\" } List<Identifier { value: \"List\" } List<Identifier { value: \"i32\" } i32>>> = m)", statements[0].to_string());
+}
+
+#[test]
+fn test_nullable_type() {
+    let file_path = "<stdin>";
+    let src = "let a: i32? = null;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a: Identifier { value: \"i32\" } i32? = null)", statements[0].to_string());
+}
+
+#[test]
+fn test_nullable_generic_type() {
+    let file_path = "<stdin>";
+    let src = "let a: List<[u8; 4]?>? = null;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a: Identifier { value: \"List\" } List<[Identifier { value: \"u8\" } u8; 4]?>? = null)", statements[0].to_string());
+}
+
+#[test]
+fn test_function_argument_types() {
+    let file_path = "<stdin>";
+    let src = "fun apply(f: fun(i32) -> i32?, xs: List<(i32, bool)>) -> Result<i32, String> { f }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"apply\" } apply([NotOptional { name: Token { token_type: Identifier { value: \"f\" }, lexeme: \"f\", possition: (1, 10, 11) }, _type: Function { arguments: [Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (1, 17, 20) } }], returned: Nullable { inner: Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (1, 25, 28) } } } } }, NotOptional { name: Token { token_type: Identifier { value: \"xs\" }, lexeme: \"xs\", possition: (1, 31, 33) }, _type: Generic { name: Token { token_type: Identifier { value: \"List\" }, lexeme: \"List\", possition: (1, 35, 39) }, arguments: [Tuple { elements: [Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (1, 41, 44) } }, Named { name: Token { token_type: Identifier { value: \"bool\" }, lexeme: \"bool\", possition: (1, 46, 50) } }] }] } }]) -> Identifier { value: \"Result\" } Result<Identifier { value: \"i32\" } i32, Identifier { value: \"String\" } String> (block \n(assigment f)\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_generic_type_not_closed_error() {
+    let file_path = "<stdin>";
+    let src = "let a: List<i32 = xs;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `>` expected after generic arguments\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_type_without_arguments_error() {
+    let file_path = "<stdin>";
+    let src = "let a: List<> = xs;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Generic argument expected\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_match_expression() {
+    let file_path = "<stdin>";
+    let src = "match x { 1 => \"one\", 2..=9 => \"few\", _ => \"many\" }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (match x (1 => (assigment \"one\")) (2..=9 => (assigment \"few\")) (_ => (assigment \"many\"))))", statements[0].to_string());
+}
+
+#[test]
+fn test_match_bool_exhaustive() {
+    let file_path = "<stdin>";
+    let src = "match b { true => 1, false => 0 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (match b (true => (assigment 1)) (false => (assigment 0))))", statements[0].to_string());
+}
+
+#[test]
+fn test_match_bool_non_exhaustive_error() {
+    let file_path = "<stdin>";
+    let src = "match b { true => 1 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Non-exhaustive match, add `_` arm to cover all other values\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_match_typed_int_ranges_exhaustive() {
+    let file_path = "<stdin>";
+    let src = "match n { 0u8..=127u8 => 1, 128u8.. => 2 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (match n (0u8..=127u8 => (assigment 1)) (128u8.. => (assigment 2))))", statements[0].to_string());
+}
+
+#[test]
+fn test_match_typed_int_ranges_non_exhaustive_error() {
+    let file_path = "<stdin>";
+    let src = "match n { 0u8..=127u8 => 1, 100u8..=200u8 => 2 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Non-exhaustive match, add `_` arm to cover all other values\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_match_unreachable_arm_error() {
+    let file_path = "<stdin>";
+    let src = "match x { _ => 1, 2 => 3 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Unreachable match arm `2`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_match_range_to_max_int() {
+    let file_path = "<stdin>";
+    let src = "match x { 170141183460469231731687303715884105727.. => 1, _ => 2 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (match x (170141183460469231731687303715884105727.. => (assigment 1)) (_ => (assigment 2))))", statements[0].to_string());
+}
+
+#[test]
+fn test_match_tuple_patterns_with_guard() {
+    let file_path = "<stdin>";
+    let src = "match p { (0, y) if y > 2 => y, (x, 0) => x, (_, _) => 0 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (match p ((0, Identifier { value: \"y\" } y) if (equalty > y 2) => (assigment y)) ((Identifier { value: \"x\" } x, 0) => (assigment x)) ((_, _) => (assigment 0))))", statements[0].to_string());
+}
+
+#[test]
+fn test_match_tuple_bool_non_exhaustive_error() {
+    let file_path = "<stdin>";
+    let src = "match p { (true, _) => 1, (_, true) => 2 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Non-exhaustive match, add `_` arm to cover all other values\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_match_negative_range_and_block_arm() {
+    let file_path = "<stdin>";
+    let src = "match x { -5..0 => { neg() }, 0 => 0, n => n }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(assigment (match x (-5..0 => (block \n(functionUse Identifier { value: \"neg\" } neg([])\n)) (0 => (assigment 0)) (Identifier { value: \"n\" } n => (assigment n))))", statements[0].to_string());
+}
+
+#[test]
+fn test_match_pattern_types_error() {
+    let file_path = "<stdin>";
+    let src = "match x { 1 => \"a\", \"b\" => \"c\", _ => \"d\" }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Mismatched types of patterns `1` and `\"b\"`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_match_empty_range_error() {
+    let file_path = "<stdin>";
+    let src = "match x { 5..1 => 1, _ => 2 }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Range pattern `5..1` is empty\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_let_refutable_pattern_error() {
+    let file_path = "<stdin>";
+    let src = "let (a, 1): (i32, i32) = t;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Refutable pattern `(Identifier { value: \"a\" } a, 1)` can be used only in `match`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_enum_declaration() {
+    let file_path = "<stdin>";
+    let src = "enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(enum Identifier { value: \"Shape\" } Shape (Circle(Identifier { value: \"f64\" } f64)) (Rect { w: Identifier { value: \"f64\" } f64, h: Identifier { value: \"f64\" } f64 }) (Empty))", statements[0].to_string());
+}
+
+#[test]
+fn test_enum_variant_let() {
+    let file_path = "<stdin>";
+    let src = "{\n    enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }\n    let s: Shape = Shape::Rect { h: 2.0, w: 1.0 };\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(block \n(enum Identifier { value: \"Shape\" } Shape (Circle(Identifier { value: \"f64\" } f64)) (Rect { w: Identifier { value: \"f64\" } f64, h: Identifier { value: \"f64\" } f64 }) (Empty))\n(let Identifier { value: \"s\" } s: Identifier { value: \"Shape\" } Shape = (variant Shape::Rect { h: 2.0, w: 1.0 }))\n)", statements[0].to_string());
+}
+
+#[test]
+fn test_enum_function_argument() {
+    let file_path = "<stdin>";
+    let src = "{\n    enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }\n    fun area(s: Shape = Shape::Empty) -> f64 { 0.0 }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(block \n(enum Identifier { value: \"Shape\" } Shape (Circle(Identifier { value: \"f64\" } f64)) (Rect { w: Identifier { value: \"f64\" } f64, h: Identifier { value: \"f64\" } f64 }) (Empty))\n(fun Identifier { value: \"area\" } area([Optional { name: Token { token_type: Identifier { value: \"s\" }, lexeme: \"s\", possition: (3, 78, 79) }, _type: Named { name: Token { token_type: Identifier { value: \"Shape\" }, lexeme: \"Shape\", possition: (3, 81, 86) } }, value: EnumVariant { enum_name: Token { token_type: Identifier { value: \"Shape\" }, lexeme: \"Shape\", possition: (3, 89, 94) }, variant: Token { token_type: Identifier { value: \"Empty\" }, lexeme: \"Empty\", possition: (3, 96, 101) }, fields: Unit } }]) -> Identifier { value: \"f64\" } f64 (block \n(assigment 0.0)\n))\n)", statements[0].to_string());
+}
+
+#[test]
+fn test_match_enum() {
+    let file_path = "<stdin>";
+    let src = "{\n    enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }\n    match s { Shape::Circle(r) => r, Shape::Rect { w, h: 0.0 } => w, Shape::Rect { w, h } => w * h, Shape::Empty => 0.0 }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(block \n(enum Identifier { value: \"Shape\" } Shape (Circle(Identifier { value: \"f64\" } f64)) (Rect { w: Identifier { value: \"f64\" } f64, h: Identifier { value: \"f64\" } f64 }) (Empty))\n(assigment (match s (Shape::Circle(Identifier { value: \"r\" } r) => (assigment r)) (Shape::Rect { w: Identifier { value: \"w\" } w, h: 0.0 } => (assigment w)) (Shape::Rect { w: Identifier { value: \"w\" } w, h: Identifier { value: \"h\" } h } => (assigment (* w h))) (Shape::Empty => (assigment 0.0))))\n)", statements[0].to_string());
+}
+
+#[test]
+fn test_match_enum_non_exhaustive_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }\n    match s { Shape::Circle(r) => r, Shape::Empty => 0.0 }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Non-exhaustive match, add `_` arm to cover all other values\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_match_enum_unreachable_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }\n    match s { Shape::Empty => 1, Shape::Circle(_) => 2, Shape::Rect {} => 3, Shape::Empty => 4 }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Unreachable match arm `Shape::Empty`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_enum_let_type_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }\n    let s: Shape = 5;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Expected `Shape`, found `{integer}`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_enum_variant_field_type_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }\n    Shape::Circle(\"a\")\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Expected `f64`, found `String`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_enum_missing_field_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }\n    Shape::Rect { w: 1.0 }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Missing field `h` in variant `Shape::Rect`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_enum_unknown_variant_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    enum Shape { Circle(f64), Rect { w: f64, h: f64 }, Empty }\n    Shape::Square\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: No variant `Square` in enum `Shape`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_enum_duplicate_variant_error() {
+    let file_path = "<stdin>";
+    let src = "enum A { X, X }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Variant `X` is declared more than once in enum `A`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_enum_used_before_declaration() {
+    let file_path = "<stdin>";
+    let src = "fun f() { let s = Shape::A; }\nenum Shape { A }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"f\" } f([]) -> Null void (block \n(let Identifier { value: \"s\" } s = (variant Shape::A))\n))", statements[0].to_string());
+    assert_eq!("(enum Identifier { value: \"Shape\" } Shape (A))", statements[1].to_string());
+}
+
+#[test]
+fn test_interface_used_before_declaration() {
+    let file_path = "<stdin>";
+    let src = "class Square: Shape { fun area() -> i32 { return 4; } }\ninterface Shape { fun area() -> i32; }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!(2, statements.len());
+}
+
+#[test]
+fn test_enum_declared_twice_after_use_error() {
+    let file_path = "<stdin>";
+    let src = "fun f() { let s = Shape::A; }\nenum Shape { A }\nenum Shape { B }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Enum `Shape` is already declared\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_error_in_function_body_reported_once() {
+    let file_path = "<stdin>";
+    let src = "fun f() { let s = Shape::B; }\nenum Shape { A }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: No variant `B` in enum `Shape`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_interface_declaration() {
+    let file_path = "<stdin>";
+    let src = "interface Shape {\n    fun area() -> f64;\n    fun name() -> String { \"shape\" }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(interface Identifier { value: \"Shape\" } Shape\n(fun Identifier { value: \"area\" } area([]) -> Identifier { value: \"f64\" } f64)\n(fun Identifier { value: \"name\" } name([]) -> Identifier { value: \"String\" } String (block \n(assigment \"shape\")\n)))", statements[0].to_string());
+}
+
+#[test]
+fn test_class_implements_interface() {
+    let file_path = "<stdin>";
+    let src = "{\n    interface Shape {\n        fun area() -> f64;\n        fun name() -> String { \"shape\" }\n    }\n    class Circle : Shape {\n        let r: f64 = 1.0;\n        fun area() -> f64 { r * r }\n    }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(block \n(interface Identifier { value: \"Shape\" } Shape\n(fun Identifier { value: \"area\" } area([]) -> Identifier { value: \"f64\" } f64)\n(fun Identifier { value: \"name\" } name([]) -> Identifier { value: \"String\" } String (block \n(assigment \"shape\")\n)))\n(class Identifier { value: \"Circle\" } Circle : Shape\n(let Identifier { value: \"r\" } r: Identifier { value: \"f64\" } f64 = 1.0)\n(fun Identifier { value: \"area\" } area([]) -> Identifier { value: \"f64\" } f64 (block \n(assigment (* r r))\n)))\n)", statements[0].to_string());
+}
+
+#[test]
+fn test_class_missing_method_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    interface Shape {\n        fun area() -> f64;\n        fun name() -> String { \"shape\" }\n    }\n    class Circle : Shape {\n        fun name() -> String { \"circle\" }\n    }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Class `Circle` doesn't implement method `area` of interface `Shape`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_class_method_signature_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    interface Shape {\n        fun area() -> f64;\n        fun name() -> String { \"shape\" }\n    }\n    class Circle : Shape {\n        fun area(x: i32) -> f64 { 1.0 }\n    }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Method `area` of class `Circle` should be `fun() -> f64` as declared in interface `Shape`, found `fun(i32) -> f64`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_class_unknown_interface_error() {
+    let file_path = "<stdin>";
+    let src = "class Circle : Shape { }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Unknown interface `Shape`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_class_duplicate_member_error() {
+    let file_path = "<stdin>";
+    let src = "class Circle {\n    let area: f64;\n    fun area() -> f64 { 1.0 }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `area` is declared more than once in class `Circle`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_interface_type_annotation_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    interface Shape {\n        fun area() -> f64;\n        fun name() -> String { \"shape\" }\n    }\n    let s: Shape = 5;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Expected `Shape`, found `{integer}`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_function_declaration() {
+    let file_path = "<stdin>";
+    let src = "fun max<T: Ord + Eq>(a: T, b: T) -> T { return a; }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"max\" } max<T: Ord + Eq>([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (1, 21, 22) }, _type: Named { name: Token { token_type: Identifier { value: \"T\" }, lexeme: \"T\", possition: (1, 24, 25) } } }, NotOptional { name: Token { token_type: Identifier { value: \"b\" }, lexeme: \"b\", possition: (1, 27, 28) }, _type: Named { name: Token { token_type: Identifier { value: \"T\" }, lexeme: \"T\", possition: (1, 30, 31) } } }]) -> Identifier { value: \"T\" } T (block \n(return a)\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_generic_function_inferred_call() {
+    let file_path = "<stdin>";
+    let src = "{\n    fun max<T: Ord>(a: T, b: T) -> T { return a; }\n    let m: i64 = max(1, 2i64);\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(block \n(fun Identifier { value: \"max\" } max<T: Ord>([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (2, 22, 23) }, _type: Named { name: Token { token_type: Identifier { value: \"T\" }, lexeme: \"T\", possition: (2, 25, 26) } } }, NotOptional { name: Token { token_type: Identifier { value: \"b\" }, lexeme: \"b\", possition: (2, 28, 29) }, _type: Named { name: Token { token_type: Identifier { value: \"T\" }, lexeme: \"T\", possition: (2, 31, 32) } } }]) -> Identifier { value: \"T\" } T (block \n(return a)\n))\n(let Identifier { value: \"m\" } m: Identifier { value: \"i64\" } i64 = (call max 1 2i64))\n)", statements[0].to_string());
+}
+
+#[test]
+fn test_generic_function_explicit_call() {
+    let file_path = "<stdin>";
+    let src = "{\n    fun max<T: Ord>(a: T, b: T) -> T { return a; }\n    let m: i64 = max::<i64>(1, 2);\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(block \n(fun Identifier { value: \"max\" } max<T: Ord>([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (2, 22, 23) }, _type: Named { name: Token { token_type: Identifier { value: \"T\" }, lexeme: \"T\", possition: (2, 25, 26) } } }, NotOptional { name: Token { token_type: Identifier { value: \"b\" }, lexeme: \"b\", possition: (2, 28, 29) }, _type: Named { name: Token { token_type: Identifier { value: \"T\" }, lexeme: \"T\", possition: (2, 31, 32) } } }]) -> Identifier { value: \"T\" } T (block \n(return a)\n))\n(let Identifier { value: \"m\" } m: Identifier { value: \"i64\" } i64 = (call max::<Identifier { value: \"i64\" } i64> 1 2))\n)", statements[0].to_string());
+}
+
+#[test]
+fn test_generic_call_argument_mismatch_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    fun max<T: Ord>(a: T, b: T) -> T { return a; }\n    max(1, \"a\")\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Argument `b` of `max` should be `{integer}`, found `String`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_call_returned_type_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    fun max<T: Ord>(a: T, b: T) -> T { return a; }\n    let m: String = max(1, 2);\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Expected `String`, found `{integer}`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_call_bound_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    fun max<T: Ord>(a: T, b: T) -> T { return a; }\n    max(true, false)\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Type `bool` doesn't implement `Ord` required by `T` of `max`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_call_type_arguments_count_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    fun max<T: Ord>(a: T, b: T) -> T { return a; }\n    let m: i32 = max::<i32, i32>(1, 2);\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Function `max` takes 1 type arguments, found 2\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_parameter_bound_in_body() {
+    let file_path = "<stdin>";
+    let src = "{\n    fun max<T: Ord>(a: T, b: T) -> T { return a; }\n    fun first<U: Ord + Eq>(u: U) -> U { return max::<U>(u, u); }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(block \n(fun Identifier { value: \"max\" } max<T: Ord>([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (2, 22, 23) }, _type: Named { name: Token { token_type: Identifier { value: \"T\" }, lexeme: \"T\", possition: (2, 25, 26) } } }, NotOptional { name: Token { token_type: Identifier { value: \"b\" }, lexeme: \"b\", possition: (2, 28, 29) }, _type: Named { name: Token { token_type: Identifier { value: \"T\" }, lexeme: \"T\", possition: (2, 31, 32) } } }]) -> Identifier { value: \"T\" } T (block \n(return a)\n))\n(fun Identifier { value: \"first\" } first<U: Ord + Eq>([NotOptional { name: Token { token_type: Identifier { value: \"u\" }, lexeme: \"u\", possition: (3, 80, 81) }, _type: Named { name: Token { token_type: Identifier { value: \"U\" }, lexeme: \"U\", possition: (3, 83, 84) } } }]) -> Identifier { value: \"U\" } U (block \n(return (call max::<Identifier { value: \"U\" } U> u u))\n))\n)", statements[0].to_string());
+}
+
+#[test]
+fn test_generic_parameter_missing_bound_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    fun max<T: Ord>(a: T, b: T) -> T { return a; }\n    fun first<U: Eq>(u: U) -> U { return max::<U>(u, u); }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Type `U` doesn't implement `Ord` required by `T` of `max`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_unknown_bound_error() {
+    let file_path = "<stdin>";
+    let src = "fun max<T: Comparable>(a: T, b: T) -> T { return a; }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Unknown interface `Comparable`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_duplicate_parameter_error() {
+    let file_path = "<stdin>";
+    let src = "fun pair<A, A>(a: A, b: A) { }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Type parameter `A` is declared more than once\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_generic_class_with_interface_bound() {
+    let file_path = "<stdin>";
+    let src = "{\n    interface Shape {\n        fun area() -> f64;\n    }\n    class Square : Shape {\n        fun area() -> f64 { return 1.0; }\n    }\n    class Holder<T: Shape> {\n        let item: T;\n    }\n    let h: Holder<Square>;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(block \n(interface Identifier { value: \"Shape\" } Shape\n(fun Identifier { value: \"area\" } area([]) -> Identifier { value: \"f64\" } f64))\n(class Identifier { value: \"Square\" } Square : Shape\n(fun Identifier { value: \"area\" } area([]) -> Identifier { value: \"f64\" } f64 (block \n(return 1.0)\n)))\n(class Identifier { value: \"Holder\" } Holder<T: Shape>\n(let Identifier { value: \"item\" } item: Identifier { value: \"T\" } T))\n(let Identifier { value: \"h\" } h: Identifier { value: \"Holder\" } Holder<Identifier { value: \"Square\" } Square>)\n)", statements[0].to_string());
+}
+
+#[test]
+fn test_generic_class_bound_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    interface Shape {\n        fun area() -> f64;\n    }\n    class Holder<T: Shape> {\n        let item: T;\n    }\n    let h: Holder<i32>;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Type `i32` doesn't implement `Shape` required by `T` of `Holder`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_call_arguments_count_error() {
+    let file_path = "<stdin>";
+    let src = "{\n    fun add(a: i32, b: i32 = 1) -> i32 { return a; }\n    add()\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Function `add` takes 1 to 2 arguments, found 0\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_module_and_import_declarations() {
+    let file_path = "<stdin>";
+    let src = "module app.main;\nimport math.vector;\nimport { Shape, area } from geometry;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(import { Shape, area } from geometry)", statements[2].to_string());
+}
+
+#[test]
+fn test_import_after_statement_error() {
+    let file_path = "<stdin>";
+    let src = "let a: i32 = 1;\nimport math.vector;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `module` and `import` declarations should be at the beginning of file\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_module_after_import_error() {
+    let file_path = "<stdin>";
+    let src = "import math.vector;\nmodule main;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `module` declaration should be the first in file\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_import_from_without_names_error() {
+    let file_path = "<stdin>";
+    let src = "import { } from util;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Imported name expected\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_pub_function_declaration() {
+    let file_path = "<stdin>";
+    let src = "pub fun add(a: i32, b: i32) -> i32 { return a; }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(pub fun Identifier { value: \"add\" } add([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (1, 12, 13) }, _type: Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (1, 15, 18) } } }, NotOptional { name: Token { token_type: Identifier { value: \"b\" }, lexeme: \"b\", possition: (1, 20, 21) }, _type: Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (1, 23, 26) } } }]) -> Identifier { value: \"i32\" } i32 (block \n(return a)\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_pub_let_declaration() {
+    let file_path = "<stdin>";
+    let src = "pub let limit: i32 = 10;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(pub let Identifier { value: \"limit\" } limit: Identifier { value: \"i32\" } i32 = 10)", statements[0].to_string());
+}
+
+#[test]
+fn test_private_top_level_error() {
+    let file_path = "<stdin>";
+    let src = "private fun helper() { }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `private` can be used only on class members\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_pub_inside_function_error() {
+    let file_path = "<stdin>";
+    let src = "fun helper() {\n    pub let a: i32 = 1;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `pub` can be used only on top level declarations and class members\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_class_member_visibility() {
+    let file_path = "<stdin>";
+    let src = "pub class Account {\n    pub let owner: String;\n    private let balance: i32;\n    pub fun deposit(amount: i32) { }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(pub class Identifier { value: \"Account\" } Account\n(pub let Identifier { value: \"owner\" } owner: Identifier { value: \"String\" } String)\n(private let Identifier { value: \"balance\" } balance: Identifier { value: \"i32\" } i32)\n(pub fun Identifier { value: \"deposit\" } deposit([NotOptional { name: Token { token_type: Identifier { value: \"amount\" }, lexeme: \"amount\", possition: (4, 97, 103) }, _type: Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (4, 105, 108) } } }]) -> Null void (block \n)))", statements[0].to_string());
+}
+
+#[test]
+fn test_private_member_inside_class() {
+    let file_path = "<stdin>";
+    let src = "class Account {\n    private let balance: i32;\n    fun same(other: Account) -> i32 { return other.balance; }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(class Identifier { value: \"Account\" } Account\n(private let Identifier { value: \"balance\" } balance: Identifier { value: \"i32\" } i32)\n(fun Identifier { value: \"same\" } same([NotOptional { name: Token { token_type: Identifier { value: \"other\" }, lexeme: \"other\", possition: (3, 59, 64) }, _type: Named { name: Token { token_type: Identifier { value: \"Account\" }, lexeme: \"Account\", possition: (3, 66, 73) } } }]) -> Identifier { value: \"i32\" } i32 (block \n(return (get other balance))\n)))", statements[0].to_string());
+}
+
+#[test]
+fn test_private_member_outside_class_error() {
+    let file_path = "<stdin>";
+    let src = "class Account {\n    private let balance: i32;\n}\nlet account: Account? = null;\nlet balance: i32? = account?.balance;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `balance` is private in class `Account`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_private_interface_method_error() {
+    let file_path = "<stdin>";
+    let src = "interface Shape {\n    fun area() -> f64;\n}\nclass Square : Shape {\n    private fun area() -> f64 { return 1.0; }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Method `area` of class `Square` implements interface `Shape` and can't be private\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_const_declaration() {
+    let file_path = "<stdin>";
+    let src = "const MAX: u32 = 4 * 8;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(const Identifier { value: \"MAX\" } MAX: Identifier { value: \"u32\" } u32 = 32)", statements[0].to_string());
+}
+
+#[test]
+fn test_const_used_in_array_length() {
+    let file_path = "<stdin>";
+    let src = "const SIZE: usize = 2;\nconst DOUBLE: usize = SIZE * 2;\nlet buffer: [u8; DOUBLE] = [0; SIZE + 2];";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"buffer\" } buffer: [Identifier { value: \"u8\" } u8; 4] = (array 0; 4))", statements[2].to_string());
+}
+
+#[test]
+fn test_const_not_constant_error() {
+    let file_path = "<stdin>";
+    let src = "fun size() -> usize { return 4; }\nconst SIZE: usize = size();";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Initializer of `SIZE` should be known at compile time, found `(call size)`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_const_type_error() {
+    let file_path = "<stdin>";
+    let src = "const NAME: bool = \"name\";";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Expected `bool`, found `String`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_const_mut_error() {
+    let file_path = "<stdin>";
+    let src = "const mut SIZE: usize = 4;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Constant can't be `mut`, use `static mut` instead\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_const_assign_error() {
+    let file_path = "<stdin>";
+    let src = "const SIZE: usize = 4;\nSIZE = 5;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Cannot assign to constant `SIZE`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_array_length_not_constant_error() {
+    let file_path = "<stdin>";
+    let src = "fun fill(n: usize) {\n    let buffer: [u8; n] = [0; 4];\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Array length should be known at compile time, found `n`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_static_declaration() {
+    let file_path = "<stdin>";
+    let src = "static mut COUNTER: i32 = 0;\nstatic LIMIT: i32 = 10;\nstatic START: i32 = LIMIT - 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(static Identifier { value: \"START\" } START: Identifier { value: \"i32\" } i32 = 9)", statements[2].to_string());
+}
+
+#[test]
+fn test_static_mut_assign() {
+    let file_path = "<stdin>";
+    let src = "static mut COUNTER: i32 = 0;\nfun tick() {\n    COUNTER += 1\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"tick\" } tick([]) -> Null void (block \n(assigment (+= COUNTER 1))\n))", statements[1].to_string());
+}
+
+#[test]
+fn test_static_assign_error() {
+    let file_path = "<stdin>";
+    let src = "static COUNTER: i32 = 0;\nfun tick() {\n    COUNTER++\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Cannot assign to immutable static `COUNTER`, declare it as `static mut`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_static_mut_in_initializer_error() {
+    let file_path = "<stdin>";
+    let src = "static mut COUNTER: i32 = 0;\nstatic START: i32 = COUNTER;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Initializer of `START` should be known at compile time, found `COUNTER`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_const_in_function_error() {
+    let file_path = "<stdin>";
+    let src = "fun tick() {\n    const STEP: i32 = 1;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `const` can be declared only on top level\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_local_variable_hides_constant() {
+    let file_path = "<stdin>";
+    let src = "const SIZE: usize = 4;\nfun fill(SIZE: usize) {\n    SIZE = 5\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"fill\" } fill([NotOptional { name: Token { token_type: Identifier { value: \"SIZE\" }, lexeme: \"SIZE\", possition: (2, 32, 36) }, _type: Named { name: Token { token_type: Identifier { value: \"usize\" }, lexeme: \"usize\", possition: (2, 38, 43) } } }]) -> Null void (block \n(assigment (= SIZE 5))\n))", statements[1].to_string());
+}
+
+#[test]
+fn test_test_attribute() {
+    let file_path = "<stdin>";
+    let src = "#[test]\nfun check() {\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(#[test] fun Identifier { value: \"check\" } check([]) -> Null void (block \n))", statements[0].to_string());
+}
+
+#[test]
+fn test_several_attributes() {
+    let file_path = "<stdin>";
+    let src = "#[deprecated(\"use area\")] #[inline(always)]\npub fun size() -> i32 {\n    return 1;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(#[deprecated(\"use area\")] #[inline(always)] pub fun Identifier { value: \"size\" } size([]) -> Identifier { value: \"i32\" } i32 (block \n(return 1)\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_allow_attribute_on_let() {
+    let file_path = "<stdin>";
+    let src = "#[allow(unused, dead_code)]\nlet x: i32 = 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(#[allow(unused, dead_code)] let Identifier { value: \"x\" } x: Identifier { value: \"i32\" } i32 = 1)", statements[0].to_string());
+}
+
+#[test]
+fn test_attributes_on_class_members() {
+    let file_path = "<stdin>";
+    let src = "#[allow(unused)]\nclass Point {\n    #[allow(unused)]\n    let x: i32 = 0;\n    #[inline]\n    pub fun get() -> i32 {\n        return 1;\n    }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(#[allow(unused)] class Identifier { value: \"Point\" } Point\n(#[allow(unused)] let Identifier { value: \"x\" } x: Identifier { value: \"i32\" } i32 = 0)\n(#[inline] pub fun Identifier { value: \"get\" } get([]) -> Identifier { value: \"i32\" } i32 (block \n(return 1)\n)))", statements[0].to_string());
+}
+
+#[test]
+fn test_unknown_attribute_error() {
+    let file_path = "<stdin>";
+    let src = "#[unknown]\nfun a() {}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Unknown attribute `unknown`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_test_attribute_on_let_error() {
+    let file_path = "<stdin>";
+    let src = "#[test]\nlet x: i32 = 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Attribute `test` can be used only on functions\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_inline_attribute_argument_error() {
+    let file_path = "<stdin>";
+    let src = "#[inline(sometimes)]\nfun a() {}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Attribute `inline` takes no arguments, `always` or `never`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_deprecated_attribute_argument_error() {
+    let file_path = "<stdin>";
+    let src = "#[deprecated(use)]\nfun a() {}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Attribute `deprecated` takes only a string message\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_allow_unknown_lint_error() {
+    let file_path = "<stdin>";
+    let src = "#[allow(everything)]\nfun a() {}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Unknown lint `everything` in `allow`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_repeated_attribute_error() {
+    let file_path = "<stdin>";
+    let src = "#[test] #[test]\nfun a() {}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Attribute `test` is used more than once\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_attribute_on_enum_error() {
+    let file_path = "<stdin>";
+    let src = "#[allow(unused)]\nenum A { B }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Attributes can be used only on functions, `let` and classes\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_attributes_in_ast() {
+    let file_path = "<stdin>";
+    let src = "#[inline] #[deprecated(\"use area\")]\nfun size() {\n}\nlet x: i32 = 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    let attrs: Vec<String> = statements[0].attrs().iter().map(|attr| attr.to_string()).collect();
+    assert_eq!(vec!["#[inline]", "#[deprecated(\"use area\")]"], attrs);
+    assert!(statements[1].attrs().is_empty());
+}
+
+#[test]
+fn test_let_infer_integer_default() {
+    let file_path = "<stdin>";
+    let src = "let x = 5;\nlet s: bool = x;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Expected `bool`, found `i32`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_let_infer_float_default() {
+    let file_path = "<stdin>";
+    let src = "let y = 2.5;\nlet s: bool = y;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Expected `bool`, found `f64`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_let_infer_suffixed_number() {
+    let file_path = "<stdin>";
+    let src = "let z = 5u8;\nlet s: bool = z;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Expected `bool`, found `u8`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_let_infer_folded_expression() {
+    let file_path = "<stdin>";
+    let src = "let v = 1 + 2;\nlet s: bool = v;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Expected `bool`, found `i32`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_let_infer_tuple() {
+    let file_path = "<stdin>";
+    let src = "let t = (1, \"x\");\nlet s: (i32, bool) = t;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Expected `(i32, bool)`, found `(i32, String)`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_let_infer_from_variable() {
+    let file_path = "<stdin>";
+    let src = "let x = 5u16;\nlet y = x;\nlet s: bool = y;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Expected `bool`, found `u16`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_let_infer_from_call() {
+    let file_path = "<stdin>";
+    let src = "fun f() -> u16 { return 1; }\nlet v = f();\nlet s: bool = v;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Expected `bool`, found `u16`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_let_inferred_type_mismatch_error() {
+    let file_path = "<stdin>";
+    let src = "let x = 5;\nlet y: String = x;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Expected `String`, found `i32`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_let_without_type_and_value_error() {
+    let file_path = "<stdin>";
+    let src = "let x;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Type annotation or initializer expected for `x`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_let_infer_null_error() {
+    let file_path = "<stdin>";
+    let src = "let x = null;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Type of `x` can't be inferred from `null`, add type annotation\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_return_without_value() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    return;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"f\" } f([]) -> Null void (block \n(return)\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_let_without_initializer() {
+    let file_path = "<stdin>";
+    let src = "let a: u8;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a: Identifier { value: \"u8\" } u8)", statements[0].to_string());
+}
+
+#[test]
+fn test_if_condition_type_error() {
+    let file_path = "<stdin>";
+    let src = "if (1) { true }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Condition of `if` should be `bool`, found `{integer}`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_while_condition_type_error() {
+    let file_path = "<stdin>";
+    let src = "let x = \"a\";\nwhile (x) { }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Condition of `while` should be `bool`, found `String`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_let_statement_initializer_error() {
+    let file_path = "<stdin>";
+    let src = "let a: u8 = if (true) 1 else 2;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `{` expected after condition of `if`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_if_expression() {
+    let file_path = "<stdin>";
+    let src = "fun f(a: i32, b: i32) {\n    let max: i32 = if (a > b) { a } else { b };\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"f\" } f([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (1, 6, 7) }, _type: Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (1, 9, 12) } } }, NotOptional { name: Token { token_type: Identifier { value: \"b\" }, lexeme: \"b\", possition: (1, 14, 15) }, _type: Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (1, 17, 20) } } }]) -> Null void (block \n(let Identifier { value: \"max\" } max: Identifier { value: \"i32\" } i32 = (if ((equalty > a b)) (block \na\n) else (block \nb\n)))\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_if_expression_folded() {
+    let file_path = "<stdin>";
+    let src = "let x = if (true) { 1 } else { 2 };";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"x\" } x = (block \n1\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_else_if_expression_type() {
+    let file_path = "<stdin>";
+    let src = "fun f(a: bool) {\n    let x = if (a) { 1u8 } else if (!a) { 2 } else { 3 };\n    let s: bool = x;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!("<stdin>: Expected `bool`, found `u8`\n", parser.parse().unwrap_err());
+}
+
+#[test]
+fn test_block_expression() {
+    let file_path = "<stdin>";
+    let src = "fun f(a: i32) {\n    let v = {\n        a += 1;\n        if (a > 2) { a = 0 }\n        a\n    };\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"f\" } f([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (1, 6, 7) }, _type: Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (1, 9, 12) } } }]) -> Null void (block \n(let Identifier { value: \"v\" } v = (block \n(assigment (+= a 1))\n(assigment (if ((equalty > a 2)) (block \n(= a 0)\n)))\na\n))\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_loop_expression() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    let v: i32? = loop {\n        break;\n    };\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"f\" } f([]) -> Null void (block \n(let Identifier { value: \"v\" } v: Identifier { value: \"i32\" } i32? = (loop (block \n(break)\n)))\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_if_expression_branch_types_error() {
+    let file_path = "<stdin>";
+    let src = "fun f(a: bool) {\n    let x = if (a) { 1 } else { \"s\" };\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `if` and `else` have incompatible types: Mismatched types `{integer}` and `String`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_block_expression_type_error() {
+    let file_path = "<stdin>";
+    let src = "let z: String = { 5 };";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Expected `String`, found `{integer}`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_block_expression_missing_semicolon_error() {
+    let file_path = "<stdin>";
+    let src = "let z = { 5 6 };";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `;` or `}` expected after `5`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_labeled_loops() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    'outer: for (i in range[0; 10]) {\n        'inner: while (true) {\n            if (i > 5) { break 'outer; }\n            continue 'inner;\n        }\n    }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"f\" } f([]) -> Null void (block \n('outer: for (Identifier { value: \"i\" } i in (assigment (range 0..10))) (block \n('inner: while true (block \n(if ((equalty > i 5)) (block \n(break 'outer)\n))\n(continue 'inner)\n))\n))\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_loop_break_value() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    let v = 'search: loop {\n        loop {\n            break 'search 5;\n        }\n    };\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"f\" } f([]) -> Null void (block \n(let Identifier { value: \"v\" } v = ('search: loop (block \n(loop (block \n(break 'search 5)\n))\n)))\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_break_values_type_error() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    let v = loop {\n        if (true) { break 1; }\n        break \"a\";\n    };\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `break` values have incompatible types: Mismatched types `{integer}` and `String`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_break_undeclared_label_error() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    loop { break 'missing; }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Undeclared label `'missing`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_break_value_in_while_error() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    while (true) { break 1; }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `break` with a value can be used only in `loop`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_break_outside_loop_error() {
+    let file_path = "<stdin>";
+    let src = "break;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `break` can be used only inside of loop\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_break_in_lambda_error() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    loop { let g = fun () { break; }; }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `break` can be used only inside of loop\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_repeated_label_error() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    'a: loop { 'a: loop { break; } }\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Label `'a` is already used by enclosing loop\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_label_without_loop_error() {
+    let file_path = "<stdin>";
+    let src = "fun f() {\n    'a: let x = 1;\n}";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Loop expected after label `'a`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_label_at_end_of_file_error() {
+    let file_path = "<stdin>";
+    let src = "let x = { 'a";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(
+        "<stdin>: Expected expression: Token { token_type: Label { value: \"a\" }, lexeme: \"'a\", possition: (1, 10, 12) }\n",
+        parser.parse().unwrap_err()
+    );
+}
+
+#[test]
+fn test_logical_expression() {
+    let file_path = "<stdin>";
+    let src = "fun g(x: bool, y: bool) -> bool { return x || y && not x; }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"g\" } g([NotOptional { name: Token { token_type: Identifier { value: \"x\" }, lexeme: \"x\", possition: (1, 6, 7) }, _type: Named { name: Token { token_type: Identifier { value: \"bool\" }, lexeme: \"bool\", possition: (1, 9, 13) } } }, NotOptional { name: Token { token_type: Identifier { value: \"y\" }, lexeme: \"y\", possition: (1, 15, 16) }, _type: Named { name: Token { token_type: Identifier { value: \"bool\" }, lexeme: \"bool\", possition: (1, 18, 22) } } }]) -> Identifier { value: \"bool\" } bool (block \n(return (|| x (&& y (not x))))\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_logical_and_short_circuit_folding() {
+    let file_path = "<stdin>";
+    let src = "fun f() -> bool { return true; }\nlet a = false && f();";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a = false)", statements[1].to_string());
+}
+
+#[test]
+fn test_logical_or_short_circuit_folding() {
+    let file_path = "<stdin>";
+    let src = "fun f() -> bool { return true; }\nlet a = true || f();";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a = true)", statements[1].to_string());
+}
+
+#[test]
+fn test_logical_decided_by_right_operand() {
+    let file_path = "<stdin>";
+    let src = "fun f() -> bool { return true; }\nlet a = true && f();";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a = (call f))", statements[1].to_string());
+}
+
+#[test]
+fn test_logical_float_operands_error() {
+    let file_path = "<stdin>";
+    let src = "let a = 1.0 && 2.0;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Operands of `&&` should be `bool`, found `Float`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_logical_short_circuit_type_error() {
+    let file_path = "<stdin>";
+    let src = "let a = false && 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Operands of `&&` should be `bool`, found `int`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_not_integer_error() {
+    let file_path = "<stdin>";
+    let src = "let a = not 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: You can't use `int` with unary operator `not`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_chained_range_error() {
+    let file_path = "<stdin>";
+    let src = "fun f(a: i32) -> i32 { return a..a..a; }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `..` can't be chained with `..`, add parentheses\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_operator_precedence() {
+    let file_path = "<stdin>";
+    let src = "let a = 1;\nlet b = 2;\nlet c = a + b * a << 1 | b;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"c\" } c = (| (<< (+ a (* b a)) 1) b))", statements[2].to_string());
+}
+
+#[test]
+fn test_prefix_and_postfix_precedence() {
+    let file_path = "<stdin>";
+    let src = "let a = [1, 2];\nlet b = 2;\nlet c = -a[0] < b == b > 2 && !(b != 1);";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"c\" } c = (&& (equalty == (equalty < (- (index a 0)) b) (equalty > b 2)) (! (equalty != b 1))))", statements[2].to_string());
+}
+
+#[test]
+fn test_precedence_table_in_grammar() {
+    let grammar = read_file("../grammar").unwrap();
+    assert!(grammar.contains(&precedence_table()));
+}
+
+#[test]
+fn test_operator_lookup() {
+    assert_eq!(Some(UNARY), operators::prefix(&TokenType::Minus).map(|operator| operator.precedence));
+    assert_eq!(Some(TERM), operators::infix(&TokenType::Minus).map(|operator| operator.precedence));
+    assert_eq!(None, operators::prefix(&TokenType::Star));
+}
+
+#[test]
+fn test_parse_expression_entry() {
+    let file_path = "<stdin>";
+    let src = "1 + 2 * 3";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let expression = parser.parse_expression().unwrap();
+
+    assert_eq!("(+ 1 (* 2 3))", expression.to_string());
+}
+
+#[test]
+fn test_parse_expression_trailing_tokens_error() {
+    let file_path = "<stdin>";
+    let src = "1 + 2 3";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Unexpected `3` after expression at possition (1, 6, 7)".to_string()), parser.parse_expression().map(|expression| expression.to_string()));
+}
+
+#[test]
+fn test_parse_statement_entry() {
+    let file_path = "<stdin>";
+    let src = "let a = 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statement = parser.parse_statement().unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a = 1)", statement.to_string());
+}
+
+#[test]
+fn test_parse_statement_trailing_tokens_error() {
+    let file_path = "<stdin>";
+    let src = "let a = 1; let b = 2;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Unexpected `let` after statement at possition (1, 11, 14)".to_string()), parser.parse_statement());
+}
+
+#[test]
+fn test_parse_type_entry() {
+    let file_path = "<stdin>";
+    let src = "[i32]?";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let _type = parser.parse_type().unwrap();
+
+    assert_eq!("[Identifier { value: \"i32\" } i32]?", _type.to_string());
+}
+
+#[test]
+fn test_parse_type_trailing_tokens_error() {
+    let file_path = "<stdin>";
+    let src = "i32 i32";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Unexpected `i32` after type at possition (1, 4, 7)".to_string()), parser.parse_type());
+}
+
+#[test]
+fn test_parse_str() {
+    let program = platinum_core::parse_str("<stdin>", "let a = 1;\nlet b = a;").unwrap();
+
+    assert_eq!("(let Identifier { value: \"b\" } b = a)", program.items[1].statement().to_string());
+}
+
+#[test]
+fn test_parse_str_error() {
+    assert_eq!(Err("<stdin>: Expected expression: Token { token_type: Semicolon, lexeme: \";\", possition: (1, 8, 9) }\n".to_string()), platinum_core::parse_str("<stdin>", "let a = ;"));
+}
+
+#[test]
+fn test_expression_statements_in_block() {
+    let file_path = "<stdin>";
+    let src = "fun f(a: i32) -> i32 { let mut b = a; b = 2; b += 1; return b; }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(fun Identifier { value: \"f\" } f([NotOptional { name: Token { token_type: Identifier { value: \"a\" }, lexeme: \"a\", possition: (1, 6, 7) }, _type: Named { name: Token { token_type: Identifier { value: \"i32\" }, lexeme: \"i32\", possition: (1, 9, 12) } } }]) -> Identifier { value: \"i32\" } i32 (block \n(let mut Identifier { value: \"b\" } b = a)\n(assigment (= b 2))\n(assigment (+= b 1))\n(return b)\n))", statements[0].to_string());
+}
+
+#[test]
+fn test_missing_semicolon_between_statements_error() {
+    let file_path = "<stdin>";
+    let src = "let a = 1;\na = 2 a = 3";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: `;` expected after `(= a 2)`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_stray_semicolon_error() {
+    let file_path = "<stdin>";
+    let src = "let a = 1;;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert_eq!(Err("<stdin>: Statement expected, found `;`\n".to_string()), parser.parse());
+}
+
+#[test]
+fn test_bad_statement_reported_once() {
+    let file_path = "<stdin>";
+    let src = "let a = ;\nfun f() { let b = [1, 2; }\nlet c = 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let errors = parser.parse_program().unwrap_err();
+
+    assert_eq!(2, errors.lines().count());
+    assert_eq!("<stdin>: Expected expression: Token { token_type: Semicolon, lexeme: \";\", possition: (1, 8, 9) }\n<stdin>: `]` or `,` expected after array element\n", errors);
+}
+
+#[test]
+fn test_bad_if_expression_in_let_reported_once() {
+    let file_path = "<stdin>";
+    let src = "let w = if (true true) {2} else {3};\nlet v = 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let errors = parser.parse_program().unwrap_err();
+
+    assert_eq!("<stdin>: `)` expected\n", errors);
+}
+
+#[test]
+fn test_bad_block_expression_in_let_reported_once() {
+    let file_path = "<stdin>";
+    let src = "let z = { 5 6 };\nlet v = 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let errors = parser.parse_program().unwrap_err();
+
+    assert_eq!("<stdin>: `;` or `}` expected after `5`\n", errors);
+}
+
+#[test]
+fn test_bad_import_reported_once() {
+    let file_path = "<stdin>";
+    let src = "import { } from util;\nlet v = 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let errors = parser.parse_program().unwrap_err();
+
+    assert_eq!("<stdin>: Imported name expected\n", errors);
+}
+
+#[test]
+fn test_missing_semicolon_before_if_reported_once() {
+    let file_path = "<stdin>";
+    let src = "let a = 5\nif (true) { a = 1; }";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let errors = parser.parse_program().unwrap_err();
+
+    assert_eq!("<stdin>: `;` expected after variable define statement\n", errors);
+}
+
+#[test]
+fn test_semicolon_after_match_statement_optional() {
+    let file_path = "<stdin>";
+    let src = "let a = 1;\nmatch a { 1 => {}, _ => {} }\nlet b = a;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let statements = parser.parse().unwrap();
+
+    assert_eq!("(let Identifier { value: \"b\" } b = a)", statements[2].to_string());
+}
+
+#[test]
+fn test_parse_program_items() {
+    let file_path = "<stdin>";
+    let src = "const MAX: i32 = 10;\nfun f() -> i32 { return MAX; }\nlet a = f();\na = a + 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    let program = parser.parse_program().unwrap();
+
+    let kinds: Vec<bool> = program.items.iter().map(|item| item.is_declaration()).collect();
+    assert_eq!(vec![true, true, false, false], kinds);
+    assert_eq!(2, program.declarations().len());
+    assert_eq!("(program \n(assigment (= a (+ a 1)))\n)", Program { items: program.items[3..].to_vec() }.to_string());
+}
+
 // #[test]
 // fn test_expression_with_variables() {
     // let file_path = "<stdin>";
@@ -0,0 +1,197 @@
+use platinum_core::lexer::lexer::Scanner;
+use platinum_core::parser::parser::Parser;
+use platinum_core::parser::stmt::Program;
+use platinum_core::passes::fold::Fold;
+use platinum_core::passes::manager::PassManager;
+use platinum_core::passes::resolve::Resolve;
+use platinum_core::passes::type_check::TypeCheck;
+
+fn parse_program(src: &str) -> Program {
+    let file_path = "<stdin>";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    parser.parse_program().unwrap()
+}
+
+#[test]
+fn test_parse_program_is_not_folded() {
+    let program = parse_program("(38u8 + 24) - 95i16");
+
+    assert_eq!("(assigment (- (group (+ 38u8 24)) 95i16))", program.items[0].statement().to_string());
+}
+
+#[test]
+fn test_fold_pass() {
+    let mut program = parse_program("(38u8 + 24) - 95i16");
+    PassManager::new().with(Fold::default()).run(&mut program).unwrap();
+
+    assert_eq!("(assigment -33)", program.items[0].statement().to_string());
+}
+
+#[test]
+fn test_fold_pass_in_lambda_body() {
+    let mut program = parse_program("let f = (x: i32) => { return x + 2 * 3; };");
+    PassManager::new().with(Fold::default()).run(&mut program).unwrap();
+
+    assert_eq!("(let Identifier { value: \"f\" } f = (lambda (x: Identifier { value: \"i32\" } i32) (block \n(return (+ x 6))\n)))", program.items[0].statement().to_string());
+}
+
+#[test]
+fn test_type_check_pass_error() {
+    let mut program = parse_program("let a = 1;\nif (a > 0) { a = \"s\" - 1; }");
+
+    assert!(PassManager::new().run(&mut program).is_ok());
+    assert_eq!(Err("Can't use operator `-` with `String` type".to_string()), PassManager::new().with(TypeCheck::default()).run(&mut program));
+}
+
+#[test]
+fn test_type_check_pass_keeps_program() {
+    let mut program = parse_program("let a = 1 + 2;");
+    PassManager::new().with(TypeCheck::default()).run(&mut program).unwrap();
+
+    assert_eq!("(let Identifier { value: \"a\" } a = (+ 1 2))", program.items[0].statement().to_string());
+}
+
+#[test]
+fn test_parse_program_is_not_type_checked() {
+    let mut program = parse_program("let x = true + 1;");
+
+    assert_eq!(Err("Unexpected binary operator".to_string()), PassManager::standard().run(&mut program));
+}
+
+#[test]
+fn test_type_check_pass_variant_field_error() {
+    let mut program = parse_program("enum E { A(i32) }\nlet e = E::A(true);");
+
+    assert_eq!(Err("Expected `i32`, found `bool`".to_string()), PassManager::new().with(TypeCheck::default()).run(&mut program));
+}
+
+#[test]
+fn test_parse_program_does_not_check_annotations() {
+    let program = parse_program("let x: i32 = \"hi\";\nlet w = if (1) { 2 } else { 3 };");
+
+    assert_eq!(2, program.items.len());
+}
+
+#[test]
+fn test_type_check_pass_string_in_integer_error() {
+    let mut program = parse_program("let x: i32 = \"hi\";");
+
+    assert_eq!(Err("Expected `i32`, found `String`".to_string()), PassManager::new().with(TypeCheck::default()).run(&mut program));
+}
+
+#[test]
+fn test_type_check_pass_float_in_integer_error() {
+    let mut program = parse_program("let u: i32 = 1.5;");
+
+    assert_eq!(Err("Expected `i32`, found `{float}`".to_string()), PassManager::new().with(TypeCheck::default()).run(&mut program));
+}
+
+#[test]
+fn test_type_check_pass_integer_in_string_error() {
+    let mut program = parse_program("let v: String = 5;");
+
+    assert_eq!(Err("Expected `String`, found `{integer}`".to_string()), PassManager::new().with(TypeCheck::default()).run(&mut program));
+}
+
+#[test]
+fn test_type_check_pass_null_in_not_nullable_error() {
+    let mut program = parse_program("let n: i32 = null;");
+
+    assert_eq!(Err("Expected `i32`, found `null`".to_string()), PassManager::new().with(TypeCheck::default()).run(&mut program));
+}
+
+#[test]
+fn test_type_check_pass_null_in_nullable() {
+    let mut program = parse_program("let n: i32? = null;\nn = 5;");
+
+    assert!(PassManager::new().with(TypeCheck::default()).run(&mut program).is_ok());
+}
+
+#[test]
+fn test_type_check_pass_assign_to_inferred_variable_error() {
+    let mut program = parse_program("let k = 5;\nk = \"s\";");
+
+    assert_eq!(Err("Expected `i32`, found `String`".to_string()), PassManager::new().with(TypeCheck::default()).run(&mut program));
+}
+
+#[test]
+fn test_type_check_pass_condition_error() {
+    let mut program = parse_program("let w = if (1) { 2 } else { 3 };");
+
+    assert_eq!(
+        Err("Condition of `if` should be `bool`, found `{integer}`".to_string()),
+        PassManager::new().with(TypeCheck::default()).run(&mut program)
+    );
+}
+
+#[test]
+fn test_fold_pass_division_by_zero_error() {
+    let mut program = parse_program("let x = 1 / 0;");
+
+    assert_eq!(Err("Division by zero at possition (1, 10, 11)".to_string()), PassManager::new().with(Fold::default()).run(&mut program));
+}
+
+#[test]
+fn test_resolve_pass() {
+    let mut program = parse_program("fun f() -> i32 { return g(); }\nfun g() -> i32 { return 1; }\nlet a = f();");
+
+    assert!(PassManager::new().with(Resolve).run(&mut program).is_ok());
+}
+
+#[test]
+fn test_resolve_pass_undeclared_name_error() {
+    let mut program = parse_program("let a = 1;\nlet b = a + c;");
+
+    assert_eq!(Err("Undeclared name `c` at possition (2, 23, 24)".to_string()), PassManager::new().with(Resolve).run(&mut program));
+}
+
+#[test]
+fn test_standard_passes() {
+    assert_eq!(vec!["type_check", "fold"], PassManager::standard().pass_names());
+}
+
+#[test]
+fn test_parse_runs_standard_passes() {
+    let file_path = "<stdin>";
+    let src = "let a = 1;\na = \"s\" - 1;";
+
+    let mut scanner = Scanner::new(file_path, src);
+    let _ = scanner.scan_tokens();
+
+    let mut parser = Parser::new(file_path, scanner.tokens);
+    assert!(parser.parse().unwrap_err().starts_with("<stdin>: "));
+}
+
+#[test]
+fn test_parse_program_keeps_const_value() {
+    let program = parse_program("const A: i32 = 2;\nconst B: i32 = A * 3;");
+
+    assert_eq!("(const Identifier { value: \"B\" } B: Identifier { value: \"i32\" } i32 = (* A 3))", program.items[1].statement().to_string());
+}
+
+#[test]
+fn test_fold_pass_const_value() {
+    let mut program = parse_program("const A: i32 = 2;\nconst B: i32 = A * 3;");
+    PassManager::new().with(Fold::default()).run(&mut program).unwrap();
+
+    assert_eq!("(const Identifier { value: \"B\" } B: Identifier { value: \"i32\" } i32 = 6)", program.items[1].statement().to_string());
+}
+
+#[test]
+fn test_fold_pass_local_variable_hides_constant() {
+    let mut program = parse_program("const N: usize = 2;\nfun fill(N: usize) {\n    let buffer = [0; N];\n}");
+    PassManager::new().with(Fold::default()).run(&mut program).unwrap();
+
+    assert_eq!("(fun Identifier { value: \"fill\" } fill([NotOptional { name: Token { token_type: Identifier { value: \"N\" }, lexeme: \"N\", possition: (2, 29, 30) }, _type: Named { name: Token { token_type: Identifier { value: \"usize\" }, lexeme: \"usize\", possition: (2, 32, 37) } } }]) -> Null void (block \n(let Identifier { value: \"buffer\" } buffer = (array 0; N))\n))", program.items[1].statement().to_string());
+}
+
+#[test]
+fn test_type_check_pass_const_value_error() {
+    let mut program = parse_program("const A: i32 = 2;\nconst B: bool = A * 3;");
+
+    assert_eq!(Err("Expected `bool`, found `i32`".to_string()), PassManager::new().with(TypeCheck::default()).run(&mut program));
+}